@@ -1,3 +1,6 @@
+use crate::error::Error;
+use crate::multisig::has_signers;
+use crate::owner::zero_address;
 use crate::storage_types::DataKey;
 use soroban_auth::{Identifier, Signature};
 use soroban_sdk::Env;
@@ -7,9 +10,14 @@ pub fn has_administrator(env: &Env) -> bool {
     env.storage().has(key)
 }
 
+// Returns the zero address once the administrator renounced or while a signer set administers.
 pub fn read_administrator(env: &Env) -> Identifier {
     let key = DataKey::Admin;
-    env.storage().get_unchecked(key).unwrap()
+    if let Some(admin) = env.storage().get(key) {
+        admin.unwrap()
+    } else {
+        zero_address(env)
+    }
 }
 
 pub fn write_administrator(env: &Env, id: Identifier) {
@@ -17,10 +25,51 @@ pub fn write_administrator(env: &Env, id: Identifier) {
     env.storage().set(key, id);
 }
 
-pub fn check_admin(env: &Env, auth: &Signature) {
+// The administrator is either one identifier or a signer set, and never set again once renounced.
+pub fn is_initialized(env: &Env) -> bool {
+    has_administrator(env) || has_signers(env) || is_renounced(env)
+}
+
+pub fn remove_administrator(env: &Env) {
+    let key = DataKey::Admin;
+    env.storage().remove(key);
+}
+
+pub fn is_renounced(env: &Env) -> bool {
+    let key = DataKey::Renounced;
+    env.storage().has(key)
+}
+
+// Removes the administrator for good, "initialize" cannot set a new one afterwards.
+pub fn renounce_administrator(env: &Env) {
+    remove_administrator(env);
+    remove_pending_admin(env);
+    env.storage().set(DataKey::Renounced, true);
+}
+
+pub fn read_pending_admin(env: &Env) -> Option<Identifier> {
+    let key = DataKey::PendAdmin;
+    env.storage().get(key).map(|admin| admin.unwrap())
+}
+
+pub fn write_pending_admin(env: &Env, id: Identifier) {
+    let key = DataKey::PendAdmin;
+    env.storage().set(key, id);
+}
+
+pub fn remove_pending_admin(env: &Env) {
+    let key = DataKey::PendAdmin;
+    env.storage().remove(key);
+}
+
+pub fn is_admin(env: &Env, id: &Identifier) -> bool {
+    has_administrator(env) && *id == read_administrator(env)
+}
+
+pub fn check_admin(env: &Env, auth: &Signature) -> Result<(), Error> {
     let auth_id = auth.identifier(env);
-    assert!(
-        auth_id == read_administrator(env),
-        "not authorized by admin"
-    );
+    if !is_admin(env, &auth_id) {
+        return Err(Error::NotAdmin);
+    }
+    Ok(())
 }
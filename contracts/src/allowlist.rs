@@ -0,0 +1,61 @@
+use crate::storage_types::{DataKey, Presale};
+use soroban_auth::Identifier;
+use soroban_sdk::{serde::Serialize, Bytes, BytesN, Env, Vec};
+
+pub fn read_presale(env: &Env) -> Option<Presale> {
+    let key = DataKey::Presale;
+    env.storage().get(key).map(|presale| presale.unwrap())
+}
+
+pub fn write_presale(env: &Env, presale: Presale) {
+    let key = DataKey::Presale;
+    env.storage().set(key, presale);
+}
+
+// Counts the tokens "owner" minted from its presale allowance.
+pub fn read_pre_minted(env: &Env, owner: Identifier) -> i128 {
+    let key = DataKey::PreMinted(owner);
+    match env.storage().get(key) {
+        Some(minted) => minted.unwrap(),
+        None => 0,
+    }
+}
+
+pub fn write_pre_minted(env: &Env, owner: Identifier, amount: i128) {
+    let key = DataKey::PreMinted(owner.clone());
+    env.storage().set(key, read_pre_minted(env, owner) + amount);
+}
+
+// Hashes the allowlist entry granting "owner" up to "allowance" presale mints.
+pub fn leaf_hash(env: &Env, owner: &Identifier, allowance: i128) -> BytesN<32> {
+    let mut data = owner.serialize(env);
+    data.extend_from_array(&allowance.to_be_bytes());
+    env.crypto().sha256(&data)
+}
+
+// Hashes two nodes in sorted order, so that proofs do not need to say which side each node is on.
+pub fn node_hash(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (a, b) = (a.to_array(), b.to_array());
+    let mut data = Bytes::new(env);
+    if a <= b {
+        data.extend_from_array(&a);
+        data.extend_from_array(&b);
+    } else {
+        data.extend_from_array(&b);
+        data.extend_from_array(&a);
+    }
+    env.crypto().sha256(&data)
+}
+
+pub fn verify_proof(
+    env: &Env,
+    root: &BytesN<32>,
+    leaf: BytesN<32>,
+    proof: &Vec<BytesN<32>>,
+) -> bool {
+    let mut hash = leaf;
+    for node in proof.iter() {
+        hash = node_hash(env, &hash, &node.unwrap());
+    }
+    &hash == root
+}
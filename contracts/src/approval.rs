@@ -1,33 +1,69 @@
-use crate::owner::zero_address;
+use crate::error::Error;
 use crate::storage_types::DataKey;
-use crate::storage_types::{ApprovalAll, ApprovalKey};
+use crate::storage_types::{ApprovalAll, ApprovalKey, TokenApproval};
 use soroban_auth::Identifier;
 use soroban_sdk::Env;
 
-pub fn read_approval(env: &Env, id: i128) -> Identifier {
+// Approvals without an expiry are stored as expiring at u64::MAX, so they last until they are
+// reset or revoked.
+fn is_live(env: &Env, expiry: u64) -> bool {
+    env.ledger().timestamp() < expiry
+}
+
+pub fn check_expiry(env: &Env, expiry: Option<u64>) -> Result<(), Error> {
+    if !is_live(env, expiry.unwrap_or(u64::MAX)) {
+        return Err(Error::InvalidExpiry);
+    }
+    Ok(())
+}
+
+// Returns none if there is no approval for token "id" or it expired.
+pub fn read_approval(env: &Env, id: i128) -> Option<Identifier> {
     let key = DataKey::Approval(ApprovalKey::ID(id));
-    if let Some(approval) = env.storage().get(key) {
-        approval.unwrap()
+    let approval = env.storage().get::<_, TokenApproval>(key)?.unwrap();
+    if is_live(env, approval.expiry) {
+        Some(approval.operator)
     } else {
-        zero_address(&env)
+        None
     }
 }
 
+pub fn has_approval(env: &Env, id: i128) -> bool {
+    let key = DataKey::Approval(ApprovalKey::ID(id));
+    env.storage().has(key)
+}
+
 pub fn read_approval_all(env: &Env, owner: Identifier, operator: Identifier) -> bool {
     let key = DataKey::Approval(ApprovalKey::All(ApprovalAll { operator, owner }));
-    if let Some(approval) = env.storage().get(key) {
-        approval.unwrap()
+    if let Some(expiry) = env.storage().get(key) {
+        is_live(env, expiry.unwrap())
     } else {
         false
     }
 }
 
-pub fn write_approval(env: &Env, id: i128, operator: Identifier) {
+pub fn write_approval(env: &Env, id: i128, operator: Identifier, expiry: Option<u64>) {
+    let key = DataKey::Approval(ApprovalKey::ID(id));
+    let expiry = expiry.unwrap_or(u64::MAX);
+    env.storage().set(key, TokenApproval { operator, expiry });
+}
+
+pub fn remove_approval(env: &Env, id: i128) {
     let key = DataKey::Approval(ApprovalKey::ID(id));
-    env.storage().set(key, operator);
+    env.storage().remove(key);
 }
 
-pub fn write_approval_all(env: &Env, owner: Identifier, operator: Identifier, approved: bool) {
+pub fn write_approval_all(
+    env: &Env,
+    owner: Identifier,
+    operator: Identifier,
+    approved: bool,
+    expiry: Option<u64>,
+) {
     let key = DataKey::Approval(ApprovalKey::All(ApprovalAll { operator, owner }));
-    env.storage().set(key, approved);
+    if approved {
+        env.storage().set(key, expiry.unwrap_or(u64::MAX));
+    } else {
+        env.storage().remove(key);
+    }
 }
@@ -0,0 +1,17 @@
+use crate::storage_types::{Auction, DataKey};
+use soroban_sdk::Env;
+
+pub fn read_auction(env: &Env, id: i128) -> Option<Auction> {
+    let key = DataKey::Auction(id);
+    env.storage().get(key).map(|auction| auction.unwrap())
+}
+
+pub fn write_auction(env: &Env, id: i128, auction: Auction) {
+    let key = DataKey::Auction(id);
+    env.storage().set(key, auction);
+}
+
+pub fn remove_auction(env: &Env, id: i128) {
+    let key = DataKey::Auction(id);
+    env.storage().remove(key);
+}
@@ -1,4 +1,4 @@
-use crate::{interface::WriteType, storage_types::DataKey};
+use crate::{error::Error, interface::WriteType, storage_types::DataKey};
 use soroban_auth::Identifier;
 use soroban_sdk::Env;
 
@@ -33,19 +33,55 @@ pub fn increment_supply(env: &Env) {
     env.storage().set(key, read_supply(&env) + 1);
 }
 
-pub fn read_minted(env: &Env, owner: Identifier) -> bool {
+pub fn decrement_supply(env: &Env) {
+    let key = DataKey::Supply;
+    env.storage().set(key, read_supply(env) - 1);
+}
+
+pub fn read_burned(env: &Env) -> i128 {
+    let key = DataKey::Burned;
+    match env.storage().get(key) {
+        Some(burned) => burned.unwrap(),
+        None => 0,
+    }
+}
+
+pub fn increment_burned(env: &Env) {
+    let key = DataKey::Burned;
+    env.storage().set(key, read_burned(env) + 1);
+}
+
+pub fn read_next_id(env: &Env) -> i128 {
+    let key = DataKey::NextId;
+    match env.storage().get(key) {
+        Some(next_id) => next_id.unwrap(),
+        None => 1,
+    }
+}
+
+pub fn write_next_id(env: &Env, next_id: i128) {
+    let key = DataKey::NextId;
+    env.storage().set(key, next_id);
+}
+
+// Counts the tokens "owner" minted through the public mints.
+pub fn read_minted(env: &Env, owner: Identifier) -> i128 {
     let key = DataKey::Minted(owner);
     match env.storage().get(key) {
         Some(minted) => minted.unwrap(),
-        None => false,
+        None => 0,
     }
 }
 
-pub fn write_minted(env: &Env, owner: Identifier) {
-    let key = DataKey::Minted(owner);
-    env.storage().set(key, true);
+pub fn write_minted(env: &Env, owner: Identifier, amount: i128) {
+    let key = DataKey::Minted(owner.clone());
+    env.storage().set(key, read_minted(env, owner) + amount);
 }
 
-pub fn check_minted(env: &Env, owner: Identifier) {
-    assert!(!read_minted(&env, owner), "already minted");
+// Fails if minting "amount" more tokens would take "owner" over "limit".
+pub fn check_minted(env: &Env, owner: Identifier, amount: i128, limit: i128) -> Result<(), Error> {
+    if read_minted(env, owner) + amount > limit {
+        return Err(Error::AlreadyMinted);
+    }
+    Ok(())
 }
@@ -1,18 +1,67 @@
-use crate::admin::{check_admin, has_administrator, read_administrator, write_administrator};
-use crate::approval::{read_approval, read_approval_all, write_approval, write_approval_all};
+use crate::admin::{
+    check_admin, has_administrator, is_initialized, read_administrator, read_pending_admin,
+    remove_administrator, remove_pending_admin, renounce_administrator, write_administrator,
+    write_pending_admin,
+};
+use crate::allowlist::{
+    leaf_hash, read_pre_minted, read_presale, verify_proof, write_pre_minted, write_presale,
+};
+use crate::approval::{
+    check_expiry, has_approval, read_approval, read_approval_all, remove_approval, write_approval,
+    write_approval_all,
+};
+use crate::auction::{read_auction, remove_auction, write_auction};
 use crate::balance::{
-    check_minted, increment_supply, read_balance, read_supply, write_balance, write_minted,
+    check_minted, decrement_supply, increment_burned, increment_supply, read_balance, read_burned,
+    read_minted, read_next_id, read_supply, write_balance, write_minted, write_next_id,
+};
+use crate::enumerable::{
+    add_token_to_enumeration, add_token_to_owner_enumeration, read_token_by_index,
+    read_token_of_owner_by_index, remove_token_from_enumeration,
+    remove_token_from_owner_enumeration,
 };
+use crate::error::Error;
 use crate::event;
-use crate::interface::{NonFungibleTokenTrait, WriteType};
+use crate::interface::{NonFungibleTokenTrait, TokenState, WriteType};
+use crate::market::{read_listing, remove_listing, write_listing};
 use crate::metadata::{
-    get_rand_uri, read_name, read_symbol, read_token_uri, write_name, write_symbol, write_token_uri,
+    add_pool_uri, assign_token_uri, read_base_uri, read_hidden_uri, read_name, read_symbol,
+    read_token_uri, read_uri_pool, read_uri_suffix, remove_pool_uri, remove_token_uri,
+    write_base_uri, write_default_hidden_uri, write_default_uri_pool, write_hidden_uri, write_name,
+    write_symbol, write_token_uri, write_uri_suffix,
+};
+use crate::multisig::{
+    check_signers, read_signers, read_signers_nonce, remove_signers,
+    verify_signers_and_consume_nonce, write_signers,
+};
+use crate::owner::{
+    check_exists, check_not_escrowed, check_not_minted, check_owner, check_recipient,
+    escrow_address, read_owner, read_token_state, write_owner, zero_address,
+};
+use crate::pause::{check_not_paused, is_paused, write_paused};
+use crate::payment::{deposit, pay_from_escrow, pay_with_royalty, refund};
+use crate::random::{is_revealed, read_seed_hash, remove_minter, write_seed, write_seed_hash};
+use crate::receiver::check_received;
+use crate::role::{check_grantable, check_role, has_role, write_role};
+use crate::royalty::{
+    check_royalty, read_royalty, remove_token_royalty, royalty_info, write_royalty,
+    write_token_royalty,
+};
+use crate::sale::{
+    check_presale, check_sale, collect_proceeds, read_active_presale, read_active_sale,
+    read_proceeds, read_sale, write_proceeds, write_sale,
 };
-use crate::owner::{check_owner, read_owner, write_owner, zero_address};
-use crate::storage_types::DataKey;
+use crate::storage_types::{
+    Auction, BaseUri, DataKey, Operation, Presale, Price, QueuedOp, Role, Royalty, Sale, Signers,
+};
+use crate::timelock::{
+    add_queued, check_delay, check_not_timelocked, next_op_id, read_delay, read_eta, read_queue,
+    read_queued, remove_queue, remove_queued, write_delay,
+};
+use crate::version::{read_version, write_version, STORAGE_VERSION};
 use soroban_auth::verify;
 use soroban_auth::{Identifier, Signature};
-use soroban_sdk::{contractimpl, symbol, Bytes, Env};
+use soroban_sdk::{contractimpl, symbol, Bytes, BytesN, Env, Vec};
 
 pub struct NonFungibleToken;
 
@@ -21,34 +70,232 @@ fn read_nonce(env: &Env, id: &Identifier) -> i128 {
     env.storage().get(key).unwrap_or(Ok(0)).unwrap()
 }
 
-fn verify_and_consume_nonce(env: &Env, auth: &Signature, expected_nonce: i128) {
+fn verify_and_consume_nonce(
+    env: &Env,
+    auth: &Signature,
+    expected_nonce: i128,
+) -> Result<(), Error> {
     match auth {
         Signature::Invoker => {
             if expected_nonce != 0 {
-                panic!("nonce should be zero for Invoker")
+                return Err(Error::IncorrectNonce);
             }
-            return;
+            return Ok(());
         }
         _ => {}
     }
 
     let id = auth.identifier(env);
+    // The zero key is a small-order point, so signatures for it are easy to forge.
+    if id == zero_address(env) {
+        return Err(Error::NotAuthorized);
+    }
     let key = DataKey::Nonce(id.clone());
     let nonce = read_nonce(env, &id);
 
-    assert!(nonce == expected_nonce, "incorrect nonce");
+    if nonce != expected_nonce {
+        return Err(Error::IncorrectNonce);
+    }
 
     env.storage().set(key, &nonce + 1);
+    Ok(())
+}
+
+// Returns whether "spender" may move token "id" of "owner" on their behalf.
+fn is_approved(env: &Env, owner: Identifier, spender: &Identifier, id: i128) -> bool {
+    matches!(read_approval(env, id), Some(operator) if operator == *spender)
+        || read_approval_all(env, owner, spender.clone())
+}
+
+// Resets the approval for token "id", which must happen whenever its owner changes.
+fn clear_approval(env: &Env, id: i128) {
+    if has_approval(env, id) {
+        remove_approval(env, id);
+        event::approve(env, zero_address(env), id);
+    }
+}
+
+// Cancels the listing of token "id" by "owner", which must happen whenever its owner changes.
+fn clear_listing(env: &Env, owner: Identifier, id: i128) {
+    if read_listing(env, id).is_some() {
+        remove_listing(env, id);
+        event::delist(env, owner, id);
+    }
+}
+
+// Moves token "id" from "from" to "to". Every transfer path must go through here, except
+// "start_auc" which moves the token into escrow.
+fn transfer(env: &Env, from: Identifier, to: Identifier, id: i128) -> Result<(), Error> {
+    check_recipient(env, &to)?;
+    move_token(env, from, to, id)
+}
+
+fn move_token(env: &Env, from: Identifier, to: Identifier, id: i128) -> Result<(), Error> {
+    // Moving a token to its owner would update the owner index twice.
+    if from == to {
+        return Err(Error::SelfTransfer);
+    }
+
+    clear_approval(env, id);
+    clear_listing(env, from.clone(), id);
+
+    remove_token_from_owner_enumeration(env, from.clone(), id);
+    add_token_to_owner_enumeration(env, to.clone(), id);
+
+    write_owner(env, id, to.clone());
+    write_balance(env, from.clone(), WriteType::Remove);
+    write_balance(env, to.clone(), WriteType::Add);
+
+    event::transfer(env, from, to, id);
+    Ok(())
+}
+
+// Returns the lowest unused id from the public mint counter onwards and advances the counter.
+fn next_token_id(env: &Env) -> i128 {
+    // Skip ids that were already minted by the administrator.
+    let mut next_id = read_next_id(env);
+    while read_token_state(env, next_id) != TokenState::NotMinted {
+        next_id += 1;
+    }
+    write_next_id(env, next_id + 1);
+    next_id
+}
+
+// Checks that batch "ids" pair up with "to" and contain no id twice.
+fn check_batch(to: &Vec<Identifier>, ids: &Vec<i128>) -> Result<(), Error> {
+    if to.len() != ids.len() {
+        return Err(Error::LengthMismatch);
+    }
+    for (index, id) in ids.iter().enumerate() {
+        if ids.first_index_of(id.unwrap()) != Some(index as u32) {
+            return Err(Error::DuplicateId);
+        }
+    }
+    Ok(())
+}
+
+// Returns the open sale if "to" may mint one more token in it. Both public mints share the
+// per-wallet limit.
+fn check_sale_mint(env: &Env, to: &Identifier) -> Result<Sale, Error> {
+    let sale = read_active_sale(env)?;
+    check_minted(env, to.clone(), 1, sale.limit)?;
+    check_max_supply(env, sale.max_supply)?;
+    Ok(sale)
+}
+
+// Checks that one more token fits within "max_supply".
+fn check_max_supply(env: &Env, max_supply: i128) -> Result<(), Error> {
+    // Burned tokens still count so that burning never reopens the sale.
+    if read_supply(env) + read_burned(env) >= max_supply {
+        return Err(Error::SoldOut);
+    }
+    Ok(())
+}
+
+// Creates token "id", which must not have been minted, for "to". Every mint path must go through
+// here.
+fn mint_token(env: &Env, to: Identifier, id: i128, uri: Option<Bytes>) -> Result<(), Error> {
+    check_recipient(env, &to)?;
+    // Nothing set up for the id before it existed may carry over to its first owner.
+    clear_approval(env, id);
+    clear_listing(env, zero_address(env), id);
+
+    add_token_to_owner_enumeration(env, to.clone(), id);
+    add_token_to_enumeration(env, id);
+
+    write_balance(env, to.clone(), WriteType::Add);
+    write_owner(env, id, to.clone());
+    increment_supply(env);
+
+    assign_token_uri(env, id, to.clone(), uri)?;
+
+    event::mint(env, to, id);
+    Ok(())
+}
+
+fn burn_token(env: &Env, from: Identifier, id: i128) {
+    remove_token_from_owner_enumeration(env, from.clone(), id);
+    remove_token_from_enumeration(env, id);
+
+    // The zero address owner is kept as a tombstone so that the id is never minted again.
+    write_owner(env, id, zero_address(env));
+    write_balance(env, from.clone(), WriteType::Remove);
+    clear_approval(env, id);
+    clear_listing(env, from.clone(), id);
+    remove_token_uri(env, id);
+    remove_minter(env, id);
+    remove_token_royalty(env, id);
+    decrement_supply(env);
+    increment_burned(env);
+
+    event::burn(env, from, id);
+}
+
+// Drops every queued operation, which must happen whenever administration changes hands so that
+// operations queued under the previous administration never run.
+fn cancel_queued(env: &Env) {
+    let admin = read_administrator(env);
+    for op in read_queue(env).iter() {
+        event::cancelled(env, admin.clone(), op.unwrap().id);
+    }
+    remove_queue(env);
+}
+
+fn execute_operation(env: &Env, op: Operation) {
+    let admin = read_administrator(env);
+    match op {
+        // Like "prop_admin", so the new administrator still has to accept.
+        Operation::SetAdmin(new_admin) => {
+            write_pending_admin(env, new_admin.clone());
+            event::propose_admin(env, admin, new_admin);
+        }
+        Operation::SetBase(BaseUri { base, suffix }) => {
+            write_base_uri(env, base.clone());
+            write_uri_suffix(env, suffix.clone());
+            event::metadata_update(env, admin, base, suffix);
+        }
+        Operation::SetRoyal(royalty) => {
+            write_royalty(env, royalty.clone());
+            event::set_royalty(env, admin, royalty.receiver, royalty.bps);
+        }
+        Operation::SetDelay(delay) => {
+            write_delay(env, delay);
+            // A pending proposal predates the delay and would let its accept skip the queue.
+            if delay > 0 {
+                if let Some(pending_admin) = read_pending_admin(env) {
+                    remove_pending_admin(env);
+                    event::cancel_admin(env, admin, pending_admin);
+                }
+            }
+        }
+    }
 }
 
 #[contractimpl]
 impl NonFungibleTokenTrait for NonFungibleToken {
-    fn initialize(env: Env, admin: Identifier, name: Bytes, symbol: Bytes) {
-        assert!(!has_administrator(&env), "already initialized");
+    fn initialize(
+        env: Env,
+        admin: Identifier,
+        name: Bytes,
+        symbol: Bytes,
+        seed_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        if is_initialized(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
 
         write_administrator(&env, admin);
         write_name(&env, name);
         write_symbol(&env, symbol);
+        write_default_uri_pool(&env);
+        write_default_hidden_uri(&env);
+        write_seed_hash(&env, seed_hash);
+        write_version(&env, STORAGE_VERSION);
+        Ok(())
+    }
+
+    fn version(env: Env) -> u32 {
+        read_version(&env)
     }
 
     fn nonce(env: Env, id: Identifier) -> i128 {
@@ -59,21 +306,300 @@ impl NonFungibleTokenTrait for NonFungibleToken {
         read_administrator(&env)
     }
 
-    fn set_admin(env: Env, admin: Signature, nonce: i128, new_admin: Identifier) {
-        check_admin(&env, &admin);
+    fn pend_admin(env: Env) -> Option<Identifier> {
+        read_pending_admin(&env)
+    }
 
-        verify_and_consume_nonce(&env, &admin, nonce);
+    fn prop_admin(
+        env: Env,
+        admin: Signature,
+        nonce: i128,
+        new_admin: Identifier,
+    ) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_not_timelocked(&env)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
 
         let admin_id = admin.identifier(&env);
 
         verify(
             &env,
             &admin,
-            symbol!("set_admin"),
+            symbol!("prop_admin"),
             (&admin_id, nonce, &new_admin),
         );
+        write_pending_admin(&env, new_admin.clone());
+        event::propose_admin(&env, admin_id, new_admin);
+        Ok(())
+    }
+
+    fn accept_adm(env: Env, new_admin: Signature, nonce: i128) -> Result<(), Error> {
+        let new_admin_id = new_admin.identifier(&env);
+        if read_pending_admin(&env) != Some(new_admin_id.clone()) {
+            return Err(Error::NotPending);
+        }
+        verify_and_consume_nonce(&env, &new_admin, nonce)?;
+
+        verify(
+            &env,
+            &new_admin,
+            symbol!("accept_adm"),
+            (&new_admin_id, nonce),
+        );
+        cancel_queued(&env);
+        let admin_id = read_administrator(&env);
+        write_administrator(&env, new_admin_id.clone());
+        remove_pending_admin(&env);
+        event::set_admin(&env, admin_id, new_admin_id);
+        Ok(())
+    }
+
+    fn cancel_adm(env: Env, admin: Signature, nonce: i128) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        let pending_admin = read_pending_admin(&env).ok_or(Error::NotPending)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(&env, &admin, symbol!("cancel_adm"), (&admin_id, nonce));
+        remove_pending_admin(&env);
+        event::cancel_admin(&env, admin_id, pending_admin);
+        Ok(())
+    }
+
+    fn signers(env: Env) -> Option<Signers> {
+        read_signers(&env)
+    }
+
+    fn sign_nonce(env: Env) -> i128 {
+        read_signers_nonce(&env)
+    }
+
+    fn set_sigs(env: Env, admin: Signature, nonce: i128, signers: Signers) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_not_timelocked(&env)?;
+        check_signers(&signers)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("set_sigs"),
+            (&admin_id, nonce, &signers),
+        );
+        cancel_queued(&env);
+        remove_administrator(&env);
+        remove_pending_admin(&env);
+        write_signers(&env, signers.clone());
+        event::set_signers(&env, admin_id, signers);
+        Ok(())
+    }
+
+    fn msig_admin(
+        env: Env,
+        sigs: Vec<Signature>,
+        nonce: i128,
+        new_admin: Identifier,
+    ) -> Result<(), Error> {
+        verify_signers_and_consume_nonce(&env, &sigs, nonce, symbol!("msig_admin"), (&new_admin,))?;
+
+        cancel_queued(&env);
+        remove_signers(&env);
         write_administrator(&env, new_admin.clone());
-        event::set_admin(&env, admin_id, new_admin);
+        event::multisig_admin(&env, new_admin);
+        Ok(())
+    }
+
+    fn msig_drop(
+        env: Env,
+        sigs: Vec<Signature>,
+        nonce: i128,
+        role: Role,
+        account: Identifier,
+    ) -> Result<(), Error> {
+        check_grantable(&role)?;
+        verify_signers_and_consume_nonce(
+            &env,
+            &sigs,
+            nonce,
+            symbol!("msig_drop"),
+            (role, &account),
+        )?;
+
+        write_role(&env, role, account.clone(), false);
+        event::multisig_drop_role(&env, account, role);
+        Ok(())
+    }
+
+    fn delay(env: Env) -> u64 {
+        read_delay(&env)
+    }
+
+    fn queued(env: Env) -> Vec<QueuedOp> {
+        read_queue(&env)
+    }
+
+    fn queue(env: Env, admin: Signature, nonce: i128, op: Operation) -> Result<u32, Error> {
+        check_admin(&env, &admin)?;
+        match &op {
+            Operation::SetRoyal(royalty) => check_royalty(royalty)?,
+            Operation::SetDelay(delay) => check_delay(*delay)?,
+            _ => {}
+        }
+        let eta = read_eta(&env)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(&env, &admin, symbol!("queue"), (&admin_id, nonce, &op));
+
+        let id = next_op_id(&env);
+        add_queued(
+            &env,
+            QueuedOp {
+                id,
+                op: op.clone(),
+                eta,
+            },
+        );
+        event::queued(&env, admin_id, id, op, eta);
+        Ok(id)
+    }
+
+    fn execute(env: Env, id: u32) -> Result<(), Error> {
+        if !has_administrator(&env) {
+            return Err(Error::NotAdmin);
+        }
+        let queued = read_queued(&env, id).ok_or(Error::NotQueued)?;
+        if env.ledger().timestamp() < queued.eta {
+            return Err(Error::NotReady);
+        }
+
+        remove_queued(&env, id)?;
+        execute_operation(&env, queued.op.clone());
+        event::executed(&env, id, queued.op);
+        Ok(())
+    }
+
+    fn cancel(env: Env, admin: Signature, nonce: i128, id: u32) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(&env, &admin, symbol!("cancel"), (&admin_id, nonce, id));
+
+        remove_queued(&env, id)?;
+        event::cancelled(&env, admin_id, id);
+        Ok(())
+    }
+
+    fn has_role(env: Env, role: Role, account: Identifier) -> bool {
+        has_role(&env, role, &account)
+    }
+
+    fn grant_role(
+        env: Env,
+        admin: Signature,
+        nonce: i128,
+        role: Role,
+        account: Identifier,
+    ) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_grantable(&role)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("grant_role"),
+            (&admin_id, nonce, role, &account),
+        );
+        write_role(&env, role, account.clone(), true);
+        event::grant_role(&env, admin_id, account, role);
+        Ok(())
+    }
+
+    fn drop_role(
+        env: Env,
+        admin: Signature,
+        nonce: i128,
+        role: Role,
+        account: Identifier,
+    ) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_grantable(&role)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("drop_role"),
+            (&admin_id, nonce, role, &account),
+        );
+        write_role(&env, role, account.clone(), false);
+        event::drop_role(&env, admin_id, account, role);
+        Ok(())
+    }
+
+    fn renounce(env: Env, account: Signature, nonce: i128, role: Role) -> Result<(), Error> {
+        if role == Role::Admin {
+            check_admin(&env, &account)?;
+        }
+        verify_and_consume_nonce(&env, &account, nonce)?;
+
+        let account_id = account.identifier(&env);
+
+        verify(
+            &env,
+            &account,
+            symbol!("renounce"),
+            (&account_id, nonce, role),
+        );
+        if role == Role::Admin {
+            cancel_queued(&env);
+            renounce_administrator(&env);
+        } else {
+            write_role(&env, role, account_id.clone(), false);
+        }
+        event::renounce(&env, account_id, role);
+        Ok(())
+    }
+
+    fn paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    fn pause(env: Env, pauser: Signature, nonce: i128) -> Result<(), Error> {
+        check_role(&env, &pauser, Role::Pauser)?;
+
+        verify_and_consume_nonce(&env, &pauser, nonce)?;
+
+        let pauser_id = pauser.identifier(&env);
+
+        verify(&env, &pauser, symbol!("pause"), (&pauser_id, nonce));
+        write_paused(&env, true);
+        event::pause(&env, pauser_id);
+        Ok(())
+    }
+
+    fn unpause(env: Env, pauser: Signature, nonce: i128) -> Result<(), Error> {
+        check_role(&env, &pauser, Role::Pauser)?;
+
+        verify_and_consume_nonce(&env, &pauser, nonce)?;
+
+        let pauser_id = pauser.identifier(&env);
+
+        verify(&env, &pauser, symbol!("unpause"), (&pauser_id, nonce));
+        write_paused(&env, false);
+        event::unpause(&env, pauser_id);
+        Ok(())
     }
 
     fn name(env: Env) -> Bytes {
@@ -84,28 +610,262 @@ impl NonFungibleTokenTrait for NonFungibleToken {
         read_symbol(&env)
     }
 
-    fn token_uri(env: Env, id: i128) -> Bytes {
+    fn token_uri(env: Env, id: i128) -> Result<Bytes, Error> {
+        check_exists(&env, id)?;
         read_token_uri(&env, id)
     }
 
-    fn appr(env: Env, owner: Signature, nonce: i128, operator: Identifier, id: i128) {
-        check_owner(&env, &owner.identifier(&env), id);
-        verify_and_consume_nonce(&env, &owner, nonce);
+    fn base_uri(env: Env) -> Bytes {
+        read_base_uri(&env)
+    }
+
+    fn uri_suffix(env: Env) -> Bytes {
+        read_uri_suffix(&env)
+    }
 
-        write_approval(&env, id, operator.clone());
+    fn set_base(
+        env: Env,
+        manager: Signature,
+        nonce: i128,
+        base: Bytes,
+        suffix: Bytes,
+    ) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        check_not_timelocked(&env)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("set_base"),
+            (&manager_id, nonce, &base, &suffix),
+        );
+
+        write_base_uri(&env, base.clone());
+        write_uri_suffix(&env, suffix.clone());
+        event::metadata_update(&env, manager_id, base, suffix);
+        Ok(())
+    }
+
+    fn set_uri(
+        env: Env,
+        manager: Signature,
+        nonce: i128,
+        id: i128,
+        uri: Option<Bytes>,
+    ) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        check_exists(&env, id)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("set_uri"),
+            (&manager_id, nonce, id, &uri),
+        );
+
+        match uri {
+            Some(uri) => write_token_uri(&env, id, uri),
+            None => remove_token_uri(&env, id),
+        }
+        event::set_uri(&env, manager_id, id);
+        Ok(())
+    }
+
+    fn hidden_uri(env: Env) -> Bytes {
+        read_hidden_uri(&env)
+    }
+
+    fn set_hidden(env: Env, manager: Signature, nonce: i128, uri: Bytes) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("set_hidden"),
+            (&manager_id, nonce, &uri),
+        );
+
+        write_hidden_uri(&env, uri.clone());
+        event::set_hidden(&env, manager_id, uri);
+        Ok(())
+    }
+
+    fn provenance(env: Env) -> BytesN<32> {
+        read_seed_hash(&env)
+    }
+
+    fn revealed(env: Env) -> bool {
+        is_revealed(&env)
+    }
+
+    fn reveal(env: Env, manager: Signature, nonce: i128, seed: Bytes) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("reveal"),
+            (&manager_id, nonce, &seed),
+        );
+
+        write_seed(&env, seed.clone())?;
+        event::reveal(&env, manager_id, seed);
+        Ok(())
+    }
+
+    fn uris(env: Env) -> Vec<Bytes> {
+        read_uri_pool(&env)
+    }
+
+    fn add_uri(env: Env, manager: Signature, nonce: i128, uri: Bytes) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("add_uri"),
+            (&manager_id, nonce, &uri),
+        );
+
+        add_pool_uri(&env, uri.clone())?;
+        event::add_uri(&env, manager_id, uri);
+        Ok(())
+    }
+
+    fn remove_uri(env: Env, manager: Signature, nonce: i128, uri: Bytes) -> Result<(), Error> {
+        check_role(&env, &manager, Role::Metadata)?;
+        verify_and_consume_nonce(&env, &manager, nonce)?;
+
+        let manager_id = manager.identifier(&env);
+
+        verify(
+            &env,
+            &manager,
+            symbol!("remove_uri"),
+            (&manager_id, nonce, &uri),
+        );
+
+        remove_pool_uri(&env, uri.clone())?;
+        event::remove_uri(&env, manager_id, uri);
+        Ok(())
+    }
+
+    fn supply(env: Env) -> i128 {
+        read_supply(&env)
+    }
+
+    fn token_at(env: Env, index: i128) -> Result<i128, Error> {
+        read_token_by_index(&env, index)
+    }
+
+    fn token_of(env: Env, owner: Identifier, index: i128) -> Result<i128, Error> {
+        read_token_of_owner_by_index(&env, owner, index)
+    }
+
+    fn appr(
+        env: Env,
+        owner: Signature,
+        nonce: i128,
+        operator: Identifier,
+        id: i128,
+        expiry: Option<u64>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let owner_id = owner.identifier(&env);
+        check_owner(&env, &owner_id, id)?;
+        check_expiry(&env, expiry)?;
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        verify(
+            &env,
+            &owner,
+            symbol!("appr"),
+            (&owner_id, nonce, &operator, id, expiry),
+        );
+
+        write_approval(&env, id, operator.clone(), expiry);
 
         event::approve(&env, operator, id);
+        Ok(())
     }
 
-    fn appr_all(env: Env, owner: Signature, nonce: i128, operator: Identifier, approved: bool) {
-        verify_and_consume_nonce(&env, &owner, nonce);
+    fn appr_all(
+        env: Env,
+        owner: Signature,
+        nonce: i128,
+        operator: Identifier,
+        approved: bool,
+        expiry: Option<u64>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        // The expiry only matters for granting, so a stale one never blocks revoking.
+        if approved {
+            check_expiry(&env, expiry)?;
+        }
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        let owner_id = owner.identifier(&env);
 
-        write_approval_all(&env, owner.identifier(&env), operator.clone(), approved);
-        event::approve_all(&env, operator, owner.identifier(&env))
+        verify(
+            &env,
+            &owner,
+            symbol!("appr_all"),
+            (&owner_id, nonce, &operator, approved, expiry),
+        );
+        write_approval_all(&env, owner_id.clone(), operator.clone(), approved, expiry);
+        event::approve_all(&env, operator, owner_id);
+        Ok(())
+    }
+
+    fn revoke(env: Env, owner: Signature, nonce: i128, id: i128) -> Result<(), Error> {
+        let owner_id = owner.identifier(&env);
+        check_owner(&env, &owner_id, id)?;
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        verify(&env, &owner, symbol!("revoke"), (&owner_id, nonce, id));
+
+        clear_approval(&env, id);
+        Ok(())
+    }
+
+    fn revoke_all(
+        env: Env,
+        owner: Signature,
+        nonce: i128,
+        operator: Identifier,
+    ) -> Result<(), Error> {
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        let owner_id = owner.identifier(&env);
+
+        verify(
+            &env,
+            &owner,
+            symbol!("revoke_all"),
+            (&owner_id, nonce, &operator),
+        );
+        write_approval_all(&env, owner_id.clone(), operator.clone(), false, None);
+        event::revoke_all(&env, operator, owner_id);
+        Ok(())
     }
 
     fn get_appr(env: Env, id: i128) -> Identifier {
-        read_approval(&env, id)
+        read_approval(&env, id).unwrap_or_else(|| zero_address(&env))
     }
 
     fn is_appr(env: Env, owner: Identifier, operator: Identifier) -> bool {
@@ -116,19 +876,49 @@ impl NonFungibleTokenTrait for NonFungibleToken {
         read_balance(&env, owner)
     }
 
-    fn owner(env: Env, id: i128) -> Identifier {
-        read_owner(&env, id)
+    fn owner(env: Env, id: i128) -> Result<Identifier, Error> {
+        check_exists(&env, id)?;
+        Ok(read_owner(&env, id))
+    }
+
+    fn xfer(env: Env, from: Signature, nonce: i128, to: Identifier, id: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let from_id = from.identifier(&env);
+        check_owner(&env, &from_id, id)?;
+        verify_and_consume_nonce(&env, &from, nonce)?;
+
+        verify(&env, &from, symbol!("xfer"), (&from_id, nonce, &to, id));
+
+        transfer(&env, from_id, to, id)?;
+        Ok(())
     }
 
-    fn xfer(env: Env, from: Signature, nonce: i128, to: Identifier, id: i128) {
-        check_owner(&env, &from.identifier(&env), id);
-        verify_and_consume_nonce(&env, &from, nonce);
+    fn xfer_batch(
+        env: Env,
+        from: Signature,
+        nonce: i128,
+        to: Vec<Identifier>,
+        ids: Vec<i128>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let from_id = from.identifier(&env);
+        check_batch(&to, &ids)?;
+        for id in ids.iter() {
+            check_owner(&env, &from_id, id.unwrap())?;
+        }
+        verify_and_consume_nonce(&env, &from, nonce)?;
 
-        write_owner(&env, id, to.clone());
-        write_balance(&env, from.identifier(&env), WriteType::Remove);
-        write_balance(&env, to.clone(), WriteType::Add);
+        verify(
+            &env,
+            &from,
+            symbol!("xfer_batch"),
+            (&from_id, nonce, &to, &ids),
+        );
 
-        event::transfer(&env, from.identifier(&env), to, id);
+        for (to, id) in to.iter().zip(ids.iter()) {
+            transfer(&env, from_id.clone(), to.unwrap(), id.unwrap())?;
+        }
+        Ok(())
     }
 
     fn xfer_from(
@@ -138,66 +928,581 @@ impl NonFungibleTokenTrait for NonFungibleToken {
         to: Identifier,
         nonce: i128,
         id: i128,
-    ) {
-        check_owner(&env, &from, id);
-        verify_and_consume_nonce(&env, &spender, nonce);
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_owner(&env, &from, id)?;
+        verify_and_consume_nonce(&env, &spender, nonce)?;
 
-        if spender.identifier(&env) == read_approval(&env, id)
-            || read_approval_all(&env, from.clone(), spender.identifier(&env))
-        {
-            write_approval(&env, id, zero_address(&env));
+        let spender_id = spender.identifier(&env);
+
+        verify(
+            &env,
+            &spender,
+            symbol!("xfer_from"),
+            (&spender_id, nonce, &from, &to, id),
+        );
+
+        if !is_approved(&env, from.clone(), &spender_id, id) {
+            return Err(Error::NotApproved);
+        }
 
-            write_owner(&env, id, to.clone());
-            write_balance(&env, from.clone(), WriteType::Remove);
-            write_balance(&env, to.clone(), WriteType::Add);
+        transfer(&env, from, to, id)?;
+        Ok(())
+    }
 
-            event::transfer(&env, from, to, id);
+    fn xfer_pay(
+        env: Env,
+        from: Signature,
+        from_nonce: i128,
+        to: Signature,
+        to_nonce: i128,
+        id: i128,
+        price: Price,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let from_id = from.identifier(&env);
+        let to_id = to.identifier(&env);
+        check_owner(&env, &from_id, id)?;
+        verify_and_consume_nonce(&env, &from, from_nonce)?;
+        verify_and_consume_nonce(&env, &to, to_nonce)?;
+
+        // Both parties sign the same terms so neither can change the price or the token.
+        verify(
+            &env,
+            &from,
+            symbol!("xfer_pay"),
+            (&from_id, from_nonce, &to_id, id, &price),
+        );
+        verify(
+            &env,
+            &to,
+            symbol!("xfer_pay"),
+            (&to_id, to_nonce, &from_id, id, &price),
+        );
+
+        pay_with_royalty(&env, &price, &to_id, &from_id, id)?;
+        transfer(&env, from_id, to_id, id)?;
+        Ok(())
+    }
+
+    fn listing(env: Env, id: i128) -> Option<Price> {
+        read_listing(&env, id)
+    }
+
+    fn list(env: Env, owner: Signature, nonce: i128, id: i128, price: Price) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let owner_id = owner.identifier(&env);
+        check_owner(&env, &owner_id, id)?;
+        if price.amount < 0 {
+            return Err(Error::NegativeAmount);
+        }
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        verify(
+            &env,
+            &owner,
+            symbol!("list"),
+            (&owner_id, nonce, id, &price),
+        );
+
+        write_listing(&env, id, price.clone());
+        event::list(&env, owner_id, id, price);
+        Ok(())
+    }
+
+    fn delist(env: Env, owner: Signature, nonce: i128, id: i128) -> Result<(), Error> {
+        let owner_id = owner.identifier(&env);
+        check_owner(&env, &owner_id, id)?;
+        if read_listing(&env, id).is_none() {
+            return Err(Error::NotListed);
+        }
+        verify_and_consume_nonce(&env, &owner, nonce)?;
+
+        verify(&env, &owner, symbol!("delist"), (&owner_id, nonce, id));
+
+        clear_listing(&env, owner_id, id);
+        Ok(())
+    }
+
+    fn buy(env: Env, buyer: Signature, nonce: i128, id: i128, price: Price) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let listing = read_listing(&env, id).ok_or(Error::NotListed)?;
+        // Protects the buyer from the listing being changed before the purchase lands.
+        if listing != price {
+            return Err(Error::PriceMismatch);
+        }
+        verify_and_consume_nonce(&env, &buyer, nonce)?;
+
+        let buyer_id = buyer.identifier(&env);
+
+        verify(&env, &buyer, symbol!("buy"), (&buyer_id, nonce, id, &price));
+
+        let seller = read_owner(&env, id);
+        remove_listing(&env, id);
+        pay_with_royalty(&env, &price, &buyer_id, &seller, id)?;
+        transfer(&env, seller.clone(), buyer_id.clone(), id)?;
+        event::sale(&env, seller, buyer_id, id, price);
+        Ok(())
+    }
+
+    fn auction(env: Env, id: i128) -> Option<Auction> {
+        read_auction(&env, id)
+    }
+
+    fn start_auc(
+        env: Env,
+        seller: Signature,
+        nonce: i128,
+        id: i128,
+        reserve: Price,
+        end: u64,
+        increment: i128,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let seller_id = seller.identifier(&env);
+        check_owner(&env, &seller_id, id)?;
+        if reserve.amount < 0 || increment <= 0 || end <= env.ledger().timestamp() {
+            return Err(Error::InvalidAuction);
+        }
+        verify_and_consume_nonce(&env, &seller, nonce)?;
+
+        verify(
+            &env,
+            &seller,
+            symbol!("start_auc"),
+            (&seller_id, nonce, id, &reserve, end, increment),
+        );
+
+        move_token(&env, seller_id.clone(), escrow_address(&env), id)?;
+        write_auction(
+            &env,
+            id,
+            Auction {
+                seller: seller_id.clone(),
+                reserve,
+                end,
+                increment,
+                bidder: zero_address(&env),
+                bid: 0,
+            },
+        );
+        event::auction(&env, seller_id, id, end);
+        Ok(())
+    }
+
+    fn bid(env: Env, bidder: Signature, nonce: i128, id: i128, amount: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let mut auction = read_auction(&env, id).ok_or(Error::NotAuctioned)?;
+        if env.ledger().timestamp() >= auction.end {
+            return Err(Error::AuctionEnded);
+        }
+
+        let has_bid = auction.bidder != zero_address(&env);
+        let min_bid = if has_bid {
+            auction
+                .bid
+                .checked_add(auction.increment)
+                .ok_or(Error::BidTooLow)?
+        } else {
+            auction.reserve.amount
+        };
+        if amount < min_bid {
+            return Err(Error::BidTooLow);
+        }
+        verify_and_consume_nonce(&env, &bidder, nonce)?;
+
+        let bidder_id = bidder.identifier(&env);
+
+        verify(
+            &env,
+            &bidder,
+            symbol!("bid"),
+            (&bidder_id, nonce, id, amount),
+        );
+
+        deposit(&env, &auction.reserve.token, &bidder_id, amount);
+        if has_bid {
+            refund(&env, &auction.reserve.token, &auction.bidder, auction.bid);
+        }
+
+        auction.bidder = bidder_id.clone();
+        auction.bid = amount;
+        write_auction(&env, id, auction);
+        event::bid(&env, bidder_id, id, amount);
+        Ok(())
+    }
+
+    fn settle(env: Env, id: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let auction = read_auction(&env, id).ok_or(Error::NotAuctioned)?;
+        if env.ledger().timestamp() < auction.end {
+            return Err(Error::AuctionNotEnded);
+        }
+        remove_auction(&env, id);
+
+        // Without bids the token goes back to the seller.
+        let winner = if auction.bidder == zero_address(&env) {
+            auction.seller.clone()
         } else {
-            panic!("not approved")
+            let price = Price {
+                token: auction.reserve.token,
+                amount: auction.bid,
+            };
+            pay_from_escrow(&env, &price, &auction.seller, id)?;
+            auction.bidder
+        };
+
+        transfer(&env, escrow_address(&env), winner.clone(), id)?;
+        event::settle(&env, auction.seller, winner, id, auction.bid);
+        Ok(())
+    }
+
+    fn safe_xfer(
+        env: Env,
+        from: Signature,
+        nonce: i128,
+        to: Identifier,
+        id: i128,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let from_id = from.identifier(&env);
+        check_owner(&env, &from_id, id)?;
+        verify_and_consume_nonce(&env, &from, nonce)?;
+
+        verify(
+            &env,
+            &from,
+            symbol!("safe_xfer"),
+            (&from_id, nonce, &to, id, &data),
+        );
+
+        transfer(&env, from_id.clone(), to.clone(), id)?;
+        check_received(&env, &from_id, &from_id, &to, id, &data)
+    }
+
+    fn safe_from(
+        env: Env,
+        spender: Signature,
+        from: Identifier,
+        to: Identifier,
+        nonce: i128,
+        id: i128,
+        data: Bytes,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_owner(&env, &from, id)?;
+        verify_and_consume_nonce(&env, &spender, nonce)?;
+
+        let spender_id = spender.identifier(&env);
+
+        verify(
+            &env,
+            &spender,
+            symbol!("safe_from"),
+            (&spender_id, nonce, &from, &to, id, &data),
+        );
+
+        if !is_approved(&env, from.clone(), &spender_id, id) {
+            return Err(Error::NotApproved);
         }
+
+        transfer(&env, from.clone(), to.clone(), id)?;
+        check_received(&env, &spender_id, &from, &to, id, &data)
     }
 
-    fn mint(env: Env, admin: Signature, nonce: i128, to: Identifier, id: i128) {
-        check_admin(&env, &admin);
-        verify_and_consume_nonce(&env, &admin, nonce);
+    fn mint(
+        env: Env,
+        minter: Signature,
+        nonce: i128,
+        to: Identifier,
+        id: i128,
+        uri: Option<Bytes>,
+        royalty: Option<Royalty>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_role(&env, &minter, Role::Minter)?;
+        check_not_minted(&env, id)?;
+        if let Some(royalty) = &royalty {
+            check_royalty(royalty)?;
+        }
+        verify_and_consume_nonce(&env, &minter, nonce)?;
+
+        verify(
+            &env,
+            &minter,
+            symbol!("mint"),
+            (minter.identifier(&env), nonce, &to, id, &uri, &royalty),
+        );
+
+        mint_token(&env, to, id, uri)?;
+        if let Some(royalty) = royalty {
+            write_token_royalty(&env, id, royalty);
+        }
+        Ok(())
+    }
+
+    fn msig_mint(
+        env: Env,
+        sigs: Vec<Signature>,
+        nonce: i128,
+        to: Identifier,
+        id: i128,
+        uri: Option<Bytes>,
+        royalty: Option<Royalty>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_not_minted(&env, id)?;
+        if let Some(royalty) = &royalty {
+            check_royalty(royalty)?;
+        }
+        verify_signers_and_consume_nonce(
+            &env,
+            &sigs,
+            nonce,
+            symbol!("msig_mint"),
+            (&to, id, &uri, &royalty),
+        )?;
+
+        mint_token(&env, to, id, uri)?;
+        if let Some(royalty) = royalty {
+            write_token_royalty(&env, id, royalty);
+        }
+        Ok(())
+    }
 
-        write_balance(&env, to.clone(), WriteType::Add);
-        write_owner(&env, id, to.clone());
-        increment_supply(&env);
+    fn mint_batch(
+        env: Env,
+        minter: Signature,
+        nonce: i128,
+        to: Vec<Identifier>,
+        ids: Vec<i128>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_role(&env, &minter, Role::Minter)?;
+        check_batch(&to, &ids)?;
+        for id in ids.iter() {
+            check_not_minted(&env, id.unwrap())?;
+        }
+        verify_and_consume_nonce(&env, &minter, nonce)?;
 
-        // Create psuedo randomness.
-        let uri = get_rand_uri(&env);
-        write_token_uri(&env, id, uri);
+        verify(
+            &env,
+            &minter,
+            symbol!("mint_batch"),
+            (minter.identifier(&env), nonce, &to, &ids),
+        );
 
-        event::mint(&env, to, id)
+        for (to, id) in to.iter().zip(ids.iter()) {
+            mint_token(&env, to.unwrap(), id.unwrap(), None)?;
+        }
+        Ok(())
     }
 
-    fn mint_next(env: Env) {
+    fn mint_next(env: Env) -> Result<(), Error> {
+        check_not_paused(&env)?;
         let to = Identifier::from(env.invoker());
-        check_minted(&env, to.clone());
-        write_minted(&env, to.clone());
+        let sale = check_sale_mint(&env, &to)?;
+
+        collect_proceeds(&env, sale.price, &to);
+        write_minted(&env, to.clone(), 1);
 
-        let next_id = read_supply(&env) + 1;
+        mint_token(&env, to, next_token_id(&env), None)
+    }
 
-        write_balance(&env, to.clone(), WriteType::Add);
-        write_owner(&env, next_id, to.clone());
-        increment_supply(&env);
+    fn sale(env: Env) -> Option<Sale> {
+        read_sale(&env)
+    }
 
-        // Create psuedo randomness.
-        let uri = get_rand_uri(&env);
-        write_token_uri(&env, next_id, uri);
+    fn set_sale(env: Env, admin: Signature, nonce: i128, sale: Sale) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_sale(&env, &sale)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
 
-        event::mint(&env, to, next_id)
+        let admin_id = admin.identifier(&env);
+
+        verify(&env, &admin, symbol!("set_sale"), (&admin_id, nonce, &sale));
+
+        write_sale(&env, sale.clone());
+        event::set_sale(&env, admin_id, sale);
+        Ok(())
+    }
+
+    fn minted(env: Env, owner: Identifier) -> i128 {
+        read_minted(&env, owner)
     }
 
-    fn burn(env: Env, admin: Signature, nonce: i128, id: i128) {
-        check_admin(&env, &admin);
-        verify_and_consume_nonce(&env, &admin, nonce);
+    fn mint_paid(env: Env, minter: Signature, nonce: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let minter_id = minter.identifier(&env);
+        let sale = check_sale_mint(&env, &minter_id)?;
+        verify_and_consume_nonce(&env, &minter, nonce)?;
+
+        verify(&env, &minter, symbol!("mint_paid"), (&minter_id, nonce));
 
+        collect_proceeds(&env, sale.price, &minter_id);
+        write_minted(&env, minter_id.clone(), 1);
+
+        mint_token(&env, minter_id, next_token_id(&env), None)
+    }
+
+    fn presale(env: Env) -> Option<Presale> {
+        read_presale(&env)
+    }
+
+    fn set_pre(env: Env, admin: Signature, nonce: i128, presale: Presale) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_presale(&env, &presale)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("set_pre"),
+            (&admin_id, nonce, &presale),
+        );
+
+        write_presale(&env, presale.clone());
+        event::set_presale(&env, admin_id, presale);
+        Ok(())
+    }
+
+    fn pre_minted(env: Env, owner: Identifier) -> i128 {
+        read_pre_minted(&env, owner)
+    }
+
+    fn mint_pre(
+        env: Env,
+        minter: Signature,
+        nonce: i128,
+        allowance: i128,
+        proof: Vec<BytesN<32>>,
+    ) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        let presale = read_active_presale(&env)?;
+        let minter_id = minter.identifier(&env);
+        let leaf = leaf_hash(&env, &minter_id, allowance);
+        if !verify_proof(&env, &presale.root, leaf, &proof) {
+            return Err(Error::InvalidProof);
+        }
+        if read_pre_minted(&env, minter_id.clone()) + 1 > allowance {
+            return Err(Error::AlreadyMinted);
+        }
+        // Presale mints count towards the maximum supply of the public sale.
+        if let Some(sale) = read_sale(&env) {
+            check_max_supply(&env, sale.max_supply)?;
+        }
+        verify_and_consume_nonce(&env, &minter, nonce)?;
+
+        verify(
+            &env,
+            &minter,
+            symbol!("mint_pre"),
+            (&minter_id, nonce, allowance, &proof),
+        );
+
+        collect_proceeds(&env, presale.price, &minter_id);
+        write_pre_minted(&env, minter_id.clone(), 1);
+
+        mint_token(&env, minter_id, next_token_id(&env), None)
+    }
+
+    fn proceeds(env: Env, token: BytesN<32>) -> i128 {
+        read_proceeds(&env, token)
+    }
+
+    fn withdraw(
+        env: Env,
+        admin: Signature,
+        nonce: i128,
+        token: BytesN<32>,
+        to: Identifier,
+    ) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("withdraw"),
+            (&admin_id, nonce, &token, &to),
+        );
+
+        let amount = read_proceeds(&env, token.clone());
+        write_proceeds(&env, token.clone(), 0);
+        if amount > 0 {
+            refund(&env, &token, &to, amount);
+        }
+        event::withdraw(&env, admin_id, to, token, amount);
+        Ok(())
+    }
+
+    fn burn(env: Env, spender: Signature, nonce: i128, id: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_exists(&env, id)?;
+        check_not_escrowed(&env, id)?;
+
+        let spender_id = spender.identifier(&env);
         let from = read_owner(&env, id);
-        write_owner(&env, id, zero_address(&env));
-        write_balance(&env, from.clone(), WriteType::Remove);
+        if !has_role(&env, Role::Burner, &spender_id)
+            && spender_id != from
+            && !is_approved(&env, from.clone(), &spender_id, id)
+        {
+            return Err(Error::NotAuthorized);
+        }
+
+        verify_and_consume_nonce(&env, &spender, nonce)?;
+
+        verify(&env, &spender, symbol!("burn"), (&spender_id, nonce, id));
+
+        burn_token(&env, from, id);
+        Ok(())
+    }
+
+    fn msig_burn(env: Env, sigs: Vec<Signature>, nonce: i128, id: i128) -> Result<(), Error> {
+        check_not_paused(&env)?;
+        check_exists(&env, id)?;
+        check_not_escrowed(&env, id)?;
+        verify_signers_and_consume_nonce(&env, &sigs, nonce, symbol!("msig_burn"), (id,))?;
+
+        burn_token(&env, read_owner(&env, id), id);
+        Ok(())
+    }
+
+    fn royalty(env: Env, id: i128, sale_price: i128) -> Result<(Identifier, i128), Error> {
+        check_exists(&env, id)?;
+        royalty_info(&env, id, sale_price)
+    }
+
+    fn get_royal(env: Env) -> Option<Royalty> {
+        read_royalty(&env)
+    }
+
+    fn set_royal(env: Env, admin: Signature, nonce: i128, royalty: Royalty) -> Result<(), Error> {
+        check_admin(&env, &admin)?;
+        check_not_timelocked(&env)?;
+        check_royalty(&royalty)?;
+        verify_and_consume_nonce(&env, &admin, nonce)?;
+
+        let admin_id = admin.identifier(&env);
+
+        verify(
+            &env,
+            &admin,
+            symbol!("set_royal"),
+            (&admin_id, nonce, &royalty),
+        );
+
+        write_royalty(&env, royalty.clone());
+        event::set_royalty(&env, admin_id, royalty.receiver, royalty.bps);
+        Ok(())
+    }
 
-        event::burn(&env, from, id);
+    fn burned(env: Env) -> i128 {
+        read_burned(&env)
     }
 }
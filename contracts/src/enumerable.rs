@@ -0,0 +1,100 @@
+use crate::balance::{read_balance, read_supply};
+use crate::error::Error;
+use crate::storage_types::{DataKey, IndexKey, OwnerIndex};
+use soroban_auth::Identifier;
+use soroban_sdk::Env;
+
+pub fn read_token_by_index(env: &Env, index: i128) -> Result<i128, Error> {
+    if index < 0 || index >= read_supply(env) {
+        return Err(Error::IndexOutOfBounds);
+    }
+    let key = DataKey::Index(IndexKey::Token(index));
+    Ok(env.storage().get_unchecked(key).unwrap())
+}
+
+pub fn read_token_of_owner_by_index(
+    env: &Env,
+    owner: Identifier,
+    index: i128,
+) -> Result<i128, Error> {
+    if index < 0 || index >= read_balance(env, owner.clone()) {
+        return Err(Error::IndexOutOfBounds);
+    }
+    let key = DataKey::Index(IndexKey::Owner(OwnerIndex { owner, index }));
+    Ok(env.storage().get_unchecked(key).unwrap())
+}
+
+// Must be called before the supply is incremented.
+pub fn add_token_to_enumeration(env: &Env, id: i128) {
+    let index = read_supply(env);
+    env.storage()
+        .set(DataKey::Index(IndexKey::Token(index)), id);
+    env.storage()
+        .set(DataKey::Index(IndexKey::TokenOf(id)), index);
+}
+
+// Must be called before the supply is decremented.
+pub fn remove_token_from_enumeration(env: &Env, id: i128) {
+    let last_index = read_supply(env) - 1;
+    let index: i128 = env
+        .storage()
+        .get_unchecked(DataKey::Index(IndexKey::TokenOf(id)))
+        .unwrap();
+
+    // Move the last token into the freed slot to keep the index dense.
+    if index != last_index {
+        let last_id: i128 = env
+            .storage()
+            .get_unchecked(DataKey::Index(IndexKey::Token(last_index)))
+            .unwrap();
+        env.storage()
+            .set(DataKey::Index(IndexKey::Token(index)), last_id);
+        env.storage()
+            .set(DataKey::Index(IndexKey::TokenOf(last_id)), index);
+    }
+
+    env.storage()
+        .remove(DataKey::Index(IndexKey::Token(last_index)));
+    env.storage().remove(DataKey::Index(IndexKey::TokenOf(id)));
+}
+
+// Must be called before the balance of "owner" is incremented.
+pub fn add_token_to_owner_enumeration(env: &Env, owner: Identifier, id: i128) {
+    let index = read_balance(env, owner.clone());
+    let key = DataKey::Index(IndexKey::Owner(OwnerIndex { owner, index }));
+    env.storage().set(key, id);
+    env.storage()
+        .set(DataKey::Index(IndexKey::OwnerOf(id)), index);
+}
+
+// Must be called before the balance of "owner" is decremented.
+pub fn remove_token_from_owner_enumeration(env: &Env, owner: Identifier, id: i128) {
+    let last_index = read_balance(env, owner.clone()) - 1;
+    let index: i128 = env
+        .storage()
+        .get_unchecked(DataKey::Index(IndexKey::OwnerOf(id)))
+        .unwrap();
+
+    // Move the last token of "owner" into the freed slot to keep the index dense.
+    if index != last_index {
+        let last_key = DataKey::Index(IndexKey::Owner(OwnerIndex {
+            owner: owner.clone(),
+            index: last_index,
+        }));
+        let last_id: i128 = env.storage().get_unchecked(last_key).unwrap();
+        let key = DataKey::Index(IndexKey::Owner(OwnerIndex {
+            owner: owner.clone(),
+            index,
+        }));
+        env.storage().set(key, last_id);
+        env.storage()
+            .set(DataKey::Index(IndexKey::OwnerOf(last_id)), index);
+    }
+
+    let last_key = DataKey::Index(IndexKey::Owner(OwnerIndex {
+        owner,
+        index: last_index,
+    }));
+    env.storage().remove(last_key);
+    env.storage().remove(DataKey::Index(IndexKey::OwnerOf(id)));
+}
@@ -0,0 +1,57 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotAdmin = 2,
+    IncorrectNonce = 3,
+    NotOwner = 4,
+    NotApproved = 5,
+    NotAuthorized = 6,
+    AlreadyMinted = 7,
+    TokenExists = 8,
+    TokenNotFound = 9,
+    IndexOutOfBounds = 10,
+    MetadataEmpty = 11,
+    MetadataExists = 12,
+    MetadataNotFound = 13,
+    NotRevealed = 14,
+    AlreadyRevealed = 15,
+    InvalidSeed = 16,
+    InvalidRoyalty = 17,
+    NegativeAmount = 18,
+    NotListed = 19,
+    PriceMismatch = 20,
+    NotAuctioned = 21,
+    AuctionEnded = 22,
+    AuctionNotEnded = 23,
+    BidTooLow = 24,
+    InvalidAuction = 25,
+    TokenEscrowed = 26,
+    SaleNotActive = 27,
+    SoldOut = 28,
+    InvalidSale = 29,
+    InvalidProof = 30,
+    LengthMismatch = 31,
+    DuplicateId = 32,
+    NotReceived = 33,
+    Paused = 34,
+    MissingRole = 35,
+    InvalidRole = 36,
+    NotPending = 37,
+    NotSigner = 38,
+    DuplicateSigner = 39,
+    BelowThreshold = 40,
+    InvalidSigners = 41,
+    Timelocked = 42,
+    NotQueued = 43,
+    NotReady = 44,
+    InvalidExpiry = 45,
+    SelfTransfer = 46,
+    InvalidRecipient = 47,
+    PoolFrozen = 48,
+    SaleOverlap = 49,
+    InvalidDelay = 50,
+}
@@ -1,5 +1,6 @@
+use crate::storage_types::{Operation, Presale, Price, Role, Sale, Signers};
 use soroban_auth::Identifier;
-use soroban_sdk::{symbol, Env};
+use soroban_sdk::{symbol, Bytes, BytesN, Env};
 
 pub(crate) fn transfer(e: &Env, from: Identifier, to: Identifier, id: i128) {
     let topics = (symbol!("transfer"), from, to);
@@ -11,6 +12,71 @@ pub(crate) fn set_admin(e: &Env, admin: Identifier, new_admin: Identifier) {
     e.events().publish(topics, new_admin);
 }
 
+pub(crate) fn grant_role(e: &Env, admin: Identifier, account: Identifier, role: Role) {
+    let topics = (symbol!("grant_role"), admin, account);
+    e.events().publish(topics, role);
+}
+
+pub(crate) fn drop_role(e: &Env, admin: Identifier, account: Identifier, role: Role) {
+    let topics = (symbol!("drop_role"), admin, account);
+    e.events().publish(topics, role);
+}
+
+pub(crate) fn renounce(e: &Env, account: Identifier, role: Role) {
+    let topics = (symbol!("renounce"), account);
+    e.events().publish(topics, role);
+}
+
+pub(crate) fn pause(e: &Env, admin: Identifier) {
+    let topics = (symbol!("pause"), admin);
+    e.events().publish(topics, ());
+}
+
+pub(crate) fn unpause(e: &Env, admin: Identifier) {
+    let topics = (symbol!("unpause"), admin);
+    e.events().publish(topics, ());
+}
+
+pub(crate) fn propose_admin(e: &Env, admin: Identifier, pending_admin: Identifier) {
+    let topics = (symbol!("prop_admin"), admin);
+    e.events().publish(topics, pending_admin);
+}
+
+pub(crate) fn cancel_admin(e: &Env, admin: Identifier, pending_admin: Identifier) {
+    let topics = (symbol!("cancel_adm"), admin);
+    e.events().publish(topics, pending_admin);
+}
+
+pub(crate) fn set_signers(e: &Env, admin: Identifier, signers: Signers) {
+    let topics = (symbol!("set_sigs"), admin);
+    e.events().publish(topics, signers);
+}
+
+pub(crate) fn multisig_admin(e: &Env, new_admin: Identifier) {
+    let topics = (symbol!("msig_admin"),);
+    e.events().publish(topics, new_admin);
+}
+
+pub(crate) fn multisig_drop_role(e: &Env, account: Identifier, role: Role) {
+    let topics = (symbol!("msig_drop"), account);
+    e.events().publish(topics, role);
+}
+
+pub(crate) fn queued(e: &Env, admin: Identifier, id: u32, op: Operation, eta: u64) {
+    let topics = (symbol!("queued"), admin);
+    e.events().publish(topics, (id, op, eta));
+}
+
+pub(crate) fn executed(e: &Env, id: u32, op: Operation) {
+    let topics = (symbol!("executed"),);
+    e.events().publish(topics, (id, op));
+}
+
+pub(crate) fn cancelled(e: &Env, admin: Identifier, id: u32) {
+    let topics = (symbol!("cancelled"), admin);
+    e.events().publish(topics, id);
+}
+
 pub(crate) fn mint(e: &Env, to: Identifier, id: i128) {
     let topics = (symbol!("mint"), to);
     e.events().publish(topics, id);
@@ -26,6 +92,97 @@ pub(crate) fn approve(e: &Env, operator: Identifier, id: i128) {
     e.events().publish(topics, id);
 }
 
+pub(crate) fn add_uri(e: &Env, admin: Identifier, uri: Bytes) {
+    let topics = (symbol!("add_uri"), admin);
+    e.events().publish(topics, uri);
+}
+
+pub(crate) fn remove_uri(e: &Env, admin: Identifier, uri: Bytes) {
+    let topics = (symbol!("remove_uri"), admin);
+    e.events().publish(topics, uri);
+}
+
+pub(crate) fn metadata_update(e: &Env, admin: Identifier, base: Bytes, suffix: Bytes) {
+    let topics = (symbol!("meta_upd"), admin);
+    e.events().publish(topics, (base, suffix));
+}
+
+pub(crate) fn set_uri(e: &Env, admin: Identifier, id: i128) {
+    let topics = (symbol!("set_uri"), admin);
+    e.events().publish(topics, id);
+}
+
+pub(crate) fn set_hidden(e: &Env, admin: Identifier, uri: Bytes) {
+    let topics = (symbol!("set_hidden"), admin);
+    e.events().publish(topics, uri);
+}
+
+pub(crate) fn reveal(e: &Env, admin: Identifier, seed: Bytes) {
+    let topics = (symbol!("reveal"), admin);
+    e.events().publish(topics, seed);
+}
+
+pub(crate) fn set_royalty(e: &Env, admin: Identifier, receiver: Identifier, bps: u32) {
+    let topics = (symbol!("set_royal"), admin);
+    e.events().publish(topics, (receiver, bps));
+}
+
+pub(crate) fn list(e: &Env, owner: Identifier, id: i128, price: Price) {
+    let topics = (symbol!("list"), owner);
+    e.events().publish(topics, (id, price));
+}
+
+pub(crate) fn delist(e: &Env, owner: Identifier, id: i128) {
+    let topics = (symbol!("delist"), owner);
+    e.events().publish(topics, id);
+}
+
+pub(crate) fn sale(e: &Env, seller: Identifier, buyer: Identifier, id: i128, price: Price) {
+    let topics = (symbol!("sale"), seller, buyer);
+    e.events().publish(topics, (id, price));
+}
+
+pub(crate) fn auction(e: &Env, seller: Identifier, id: i128, end: u64) {
+    let topics = (symbol!("auction"), seller);
+    e.events().publish(topics, (id, end));
+}
+
+pub(crate) fn bid(e: &Env, bidder: Identifier, id: i128, amount: i128) {
+    let topics = (symbol!("bid"), bidder);
+    e.events().publish(topics, (id, amount));
+}
+
+pub(crate) fn settle(e: &Env, seller: Identifier, winner: Identifier, id: i128, amount: i128) {
+    let topics = (symbol!("settle"), seller, winner);
+    e.events().publish(topics, (id, amount));
+}
+
+pub(crate) fn set_sale(e: &Env, admin: Identifier, sale: Sale) {
+    let topics = (symbol!("set_sale"), admin);
+    e.events().publish(topics, sale);
+}
+
+pub(crate) fn set_presale(e: &Env, admin: Identifier, presale: Presale) {
+    let topics = (symbol!("set_pre"), admin);
+    e.events().publish(topics, presale);
+}
+
+pub(crate) fn withdraw(
+    e: &Env,
+    admin: Identifier,
+    to: Identifier,
+    token: BytesN<32>,
+    amount: i128,
+) {
+    let topics = (symbol!("withdraw"), admin, to);
+    e.events().publish(topics, (token, amount));
+}
+
+pub(crate) fn revoke_all(e: &Env, operator: Identifier, owner: Identifier) {
+    let topics = (symbol!("revoke_all"), operator);
+    e.events().publish(topics, owner);
+}
+
 pub(crate) fn approve_all(e: &Env, operator: Identifier, owner: Identifier) {
     let topics = (symbol!("appr_all"), operator);
     e.events().publish(topics, owner);
@@ -1,3 +1,8 @@
+use crate::error::Error;
+use crate::storage_types::{
+    Auction, Operation, Presale, Price, QueuedOp, Role, Royalty, Sale, Signers,
+};
+
 pub trait NonFungibleTokenTrait {
     // --------------------------------------------------------------------------------
     // Authentication interface
@@ -10,17 +15,166 @@ pub trait NonFungibleTokenTrait {
     // Admin interface
     // --------------------------------------------------------------------------------
 
-    /// Returns the current administrator
+    /// Returns the current administrator, or the zero address if it renounced or a signer set
+    /// administers the contract
     fn admin(env: soroban_sdk::Env) -> soroban_auth::Identifier;
 
-    /// If "admin" is the administrator, set the administrator to "new_admin".
+    /// Returns the administrator proposed by "prop_admin", if any.
+    fn pend_admin(env: soroban_sdk::Env) -> Option<soroban_auth::Identifier>;
+
+    /// If "admin" is the administrator, propose "new_admin" as the next administrator. The
+    /// administrator does not change until "new_admin" accepts with "accept_adm".
+    /// Emit event with topics = ["prop_admin", admin: Identifier], data = [new_admin: Identifier]
+    fn prop_admin(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        new_admin: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
+
+    /// If "new_admin" is the proposed administrator, make it the administrator.
     /// Emit event with topics = ["set_admin", admin: Identifier], data = [new_admin: Identifier]
-    fn set_admin(
+    fn accept_adm(
+        env: soroban_sdk::Env,
+        new_admin: soroban_auth::Signature,
+        nonce: i128,
+    ) -> Result<(), Error>;
+
+    /// If "admin" is the administrator, withdraw the proposed administrator.
+    /// Emit event with topics = ["cancel_adm", admin: Identifier], data = [new_admin: Identifier]
+    fn cancel_adm(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+    ) -> Result<(), Error>;
+
+    /// Returns the signer set administering the contract, if any.
+    fn signers(env: soroban_sdk::Env) -> Option<Signers>;
+
+    /// Returns the current nonce of the signer set, signed by every signature given to the
+    /// "msig_" functions.
+    fn sign_nonce(env: soroban_sdk::Env) -> i128;
+
+    /// If "admin" is the administrator, hand administration over to the signer set "signers".
+    /// From then on, admin actions need signatures from distinct signers whose weights reach
+    /// the threshold, given to "msig_admin", "msig_drop", "msig_mint" and "msig_burn". Roles
+    /// granted so far are kept until revoked with "msig_drop".
+    /// Emit event with topics = ["set_sigs", admin: Identifier], data = [signers: Signers]
+    fn set_sigs(
         env: soroban_sdk::Env,
         admin: soroban_auth::Signature,
         nonce: i128,
+        signers: Signers,
+    ) -> Result<(), Error>;
+
+    /// If "sigs" reach the threshold of the signer set, set the administrator to "new_admin",
+    /// replacing the signer set.
+    /// Emit event with topics = ["msig_admin"], data = [new_admin: Identifier]
+    fn msig_admin(
+        env: soroban_sdk::Env,
+        sigs: soroban_sdk::Vec<soroban_auth::Signature>,
+        nonce: i128,
         new_admin: soroban_auth::Identifier,
-    );
+    ) -> Result<(), Error>;
+
+    /// If "sigs" reach the threshold of the signer set, revoke "role" from "account" like
+    /// "drop_role". Roles granted before the signer set was installed can only be revoked this
+    /// way.
+    /// Emit event with topics = ["msig_drop", account: Identifier], data = [role: Role]
+    fn msig_drop(
+        env: soroban_sdk::Env,
+        sigs: soroban_sdk::Vec<soroban_auth::Signature>,
+        nonce: i128,
+        role: Role,
+        account: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
+
+    /// Returns the timelock delay in seconds, 0 if unset.
+    fn delay(env: soroban_sdk::Env) -> u64;
+
+    /// Returns the operations queued and not yet executed or cancelled.
+    fn queued(env: soroban_sdk::Env) -> soroban_sdk::Vec<QueuedOp>;
+
+    /// If "admin" is the administrator, queue "op" to be executed once the current delay has
+    /// passed, and return its id. While the delay is not zero, admin changes, base uri changes
+    /// and royalty changes can only be made through the queue. A queued admin change proposes
+    /// the new administrator, who still accepts with "accept_adm". Delays are capped at 30 days,
+    /// and setting a delay other than zero withdraws the proposed administrator, if any.
+    /// Emit event with topics = ["queued", admin: Identifier], data = [id: u32, op: Operation, eta: u64]
+    fn queue(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        op: Operation,
+    ) -> Result<u32, Error>;
+
+    /// Execute the queued operation "id" once its eta has passed. Anyone may execute.
+    /// Emit event with topics = ["executed"], data = [id: u32, op: Operation]
+    fn execute(env: soroban_sdk::Env, id: u32) -> Result<(), Error>;
+
+    /// If "admin" is the administrator, drop the queued operation "id". Every queued operation is
+    /// dropped this way when administration changes hands.
+    /// Emit event with topics = ["cancelled", admin: Identifier], data = [id: u32]
+    fn cancel(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        id: u32,
+    ) -> Result<(), Error>;
+
+    /// Returns true if "account" holds "role". The administrator holds every role.
+    fn has_role(env: soroban_sdk::Env, role: Role, account: soroban_auth::Identifier) -> bool;
+
+    /// If "admin" is the administrator, grant "role" to "account". The admin role cannot be
+    /// granted, use "prop_admin" instead.
+    /// Emit event with topics = ["grant_role", admin: Identifier, account: Identifier], data = [role: Role]
+    fn grant_role(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        role: Role,
+        account: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
+
+    /// If "admin" is the administrator, revoke "role" from "account".
+    /// Emit event with topics = ["drop_role", admin: Identifier, account: Identifier], data = [role: Role]
+    fn drop_role(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        role: Role,
+        account: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
+
+    /// Give up "role" held by "account". Renouncing the admin role leaves the contract without
+    /// an administrator for good, disabling every function that needs one.
+    /// Emit event with topics = ["renounce", account: Identifier], data = [role: Role]
+    fn renounce(
+        env: soroban_sdk::Env,
+        account: soroban_auth::Signature,
+        nonce: i128,
+        role: Role,
+    ) -> Result<(), Error>;
+
+    /// Returns true if the collection is paused
+    fn paused(env: soroban_sdk::Env) -> bool;
+
+    /// If "pauser" holds the pauser role, pause the collection. While paused, tokens cannot be
+    /// minted, transferred, approved, burned, listed, bought or auctioned.
+    /// Emit event with topics = ["pause", pauser: Identifier], data = []
+    fn pause(
+        env: soroban_sdk::Env,
+        pauser: soroban_auth::Signature,
+        nonce: i128,
+    ) -> Result<(), Error>;
+
+    /// If "pauser" holds the pauser role, unpause the collection.
+    /// Emit event with topics = ["unpause", pauser: Identifier], data = []
+    fn unpause(
+        env: soroban_sdk::Env,
+        pauser: soroban_auth::Signature,
+        nonce: i128,
+    ) -> Result<(), Error>;
 
     // --------------------------------------------------------------------------------
     // Metadata interface
@@ -32,14 +186,123 @@ pub trait NonFungibleTokenTrait {
     // Get the symbol for this token.
     fn symbol(env: soroban_sdk::Env) -> soroban_sdk::Bytes;
 
-    // Get the uniform resource identifier for token "id".
-    fn token_uri(env: soroban_sdk::Env, id: i128) -> soroban_sdk::Bytes;
+    // Get the uniform resource identifier for token "id". Every token shows the placeholder uri
+    // until the metadata is revealed. Fails if token "id" does not exist, or if it was minted
+    // from the pool and is not revealed yet while no placeholder is set.
+    fn token_uri(env: soroban_sdk::Env, id: i128) -> Result<soroban_sdk::Bytes, Error>;
+
+    // Get the base uniform resource identifier, empty if unset.
+    fn base_uri(env: soroban_sdk::Env) -> soroban_sdk::Bytes;
+
+    // Get the suffix appended to the base uniform resource identifier and token id.
+    fn uri_suffix(env: soroban_sdk::Env) -> soroban_sdk::Bytes;
+
+    /// If "manager" holds the metadata role, set the base uniform resource identifier and
+    /// suffix. Tokens without their own uri resolve to "base + id + suffix". While "base" is
+    /// empty, tokens are assigned a uri from the metadata pool when minted instead.
+    /// Emit event with topics = ["meta_upd", manager: Identifier], data = [base: Bytes, suffix: Bytes]
+    fn set_base(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        base: soroban_sdk::Bytes,
+        suffix: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    /// If "manager" holds the metadata role, override the uri of token "id" with "uri", or clear
+    /// the override if "uri" is none.
+    /// Emit event with topics = ["set_uri", manager: Identifier], data = [id: i128]
+    fn set_uri(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+        uri: Option<soroban_sdk::Bytes>,
+    ) -> Result<(), Error>;
+
+    // Get the placeholder uniform resource identifier shown before the reveal, "images/logo.png"
+    // unless changed with "set_hidden", or empty if cleared.
+    fn hidden_uri(env: soroban_sdk::Env) -> soroban_sdk::Bytes;
+
+    /// If "manager" holds the metadata role, set the placeholder uri shown for every token until
+    /// the metadata is revealed. An empty "uri" shows tokens with their own metadata right away.
+    /// Emit event with topics = ["set_hidden", manager: Identifier], data = [uri: Bytes]
+    fn set_hidden(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        uri: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    // Returns the provenance hash committed at initialization, the sha256 hash of the seed that
+    // maps tokens minted from the pool to their final metadata.
+    fn provenance(env: soroban_sdk::Env) -> soroban_sdk::BytesN<32>;
+
+    // Returns true if the seed assigning metadata from the pool has been revealed.
+    fn revealed(env: soroban_sdk::Env) -> bool;
+
+    /// If "manager" holds the metadata role and "seed" matches the hash committed at
+    /// initialization, reveal the metadata of tokens minted from the pool. Each token gets the
+    /// pool entry picked by hashing "seed", its minter and its id. The pool can no longer change
+    /// and no more tokens can be minted from it afterwards.
+    /// Emit event with topics = ["reveal", manager: Identifier], data = [seed: Bytes]
+    fn reveal(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        seed: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    // Get the pool of uniform resource identifiers assigned to newly minted tokens.
+    fn uris(env: soroban_sdk::Env) -> soroban_sdk::Vec<soroban_sdk::Bytes>;
+
+    /// If "manager" holds the metadata role and no token was minted from the metadata pool yet,
+    /// add "uri" to the pool.
+    /// Emit event with topics = ["add_uri", manager: Identifier], data = [uri: Bytes]
+    fn add_uri(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        uri: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    /// If "manager" holds the metadata role and no token was minted from the metadata pool yet,
+    /// remove "uri" from the pool.
+    /// Emit event with topics = ["remove_uri", manager: Identifier], data = [uri: Bytes]
+    fn remove_uri(
+        env: soroban_sdk::Env,
+        manager: soroban_auth::Signature,
+        nonce: i128,
+        uri: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    // --------------------------------------------------------------------------------
+    // Enumerable interface
+    // --------------------------------------------------------------------------------
+
+    /// Returns the number of tokens currently in existence.
+    fn supply(env: soroban_sdk::Env) -> i128;
+
+    /// Returns the token id at "index" of all tokens, where "index" is less than the total supply.
+    /// The order of tokens may change when a token is burned.
+    fn token_at(env: soroban_sdk::Env, index: i128) -> Result<i128, Error>;
+
+    /// Returns the token id at "index" of the tokens owned by "owner", where "index" is less
+    /// than the balance of "owner". The order of tokens may change when a token is transferred.
+    fn token_of(
+        env: soroban_sdk::Env,
+        owner: soroban_auth::Identifier,
+        index: i128,
+    ) -> Result<i128, Error>;
 
     // --------------------------------------------------------------------------------
     // Token interface
     // --------------------------------------------------------------------------------
 
-    /// Allows "operator" to manage token "id" if "owner" is the current owner of token "id".
+    /// Allows "operator" to manage token "id" if "owner" is the current owner of token "id",
+    /// until the ledger timestamp "expiry" if given. The approval is reset whenever token "id"
+    /// changes owner or is burned, or is revoked, emitting this event with the zero address as
+    /// "operator".
     /// Emit event with topics = ["appr", operator: Identifier], data = [id: i128]
     fn appr(
         env: soroban_sdk::Env,
@@ -47,9 +310,11 @@ pub trait NonFungibleTokenTrait {
         nonce: i128,
         operator: soroban_auth::Identifier,
         id: i128,
-    );
+        expiry: Option<u64>,
+    ) -> Result<(), Error>;
 
-    /// If "approved", allows "operator" to manage all tokens of "owner"
+    /// If "approved", allows "operator" to manage all tokens of "owner", until the ledger
+    /// timestamp "expiry" if given. Otherwise revokes the approval and ignores "expiry".
     /// Emit event with topics = ["appr_all", operator: Identifier], data = [owner: Identifier]
     fn appr_all(
         env: soroban_sdk::Env,
@@ -57,12 +322,33 @@ pub trait NonFungibleTokenTrait {
         nonce: i128,
         operator: soroban_auth::Identifier,
         approved: bool,
-    );
+        expiry: Option<u64>,
+    ) -> Result<(), Error>;
+
+    /// If "owner" is the current owner of token "id", remove the approval for token "id".
+    /// Emit event with topics = ["appr", zero_address: Identifier], data = [id: i128]
+    fn revoke(
+        env: soroban_sdk::Env,
+        owner: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+    ) -> Result<(), Error>;
+
+    /// Stop "operator" from managing the tokens of "owner".
+    /// Emit event with topics = ["revoke_all", operator: Identifier], data = [owner: Identifier]
+    fn revoke_all(
+        env: soroban_sdk::Env,
+        owner: soroban_auth::Signature,
+        nonce: i128,
+        operator: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
 
-    /// Returns the identifier approved for token "id".
+    /// Returns the identifier approved for token "id", or the zero address if the approval
+    /// expired.
     fn get_appr(env: soroban_sdk::Env, id: i128) -> soroban_auth::Identifier;
 
-    /// If "operator" is allowed to manage assets of "owner", return true.
+    /// If "operator" is allowed to manage assets of "owner" and the approval has not expired,
+    /// return true.
     fn is_appr(
         env: soroban_sdk::Env,
         owner: soroban_auth::Identifier,
@@ -72,10 +358,11 @@ pub trait NonFungibleTokenTrait {
     /// Get the balance of "id".
     fn balance(env: soroban_sdk::Env, owner: soroban_auth::Identifier) -> i128;
 
-    /// Get the owner of "id" token.
-    fn owner(env: soroban_sdk::Env, id: i128) -> soroban_auth::Identifier;
+    /// Get the owner of "id" token. Fails if token "id" does not exist.
+    fn owner(env: soroban_sdk::Env, id: i128) -> Result<soroban_auth::Identifier, Error>;
 
-    /// Transfer token "id" from "from" to "to.
+    /// Transfer token "id" from "from" to "to". Fails if "to" is "from", the zero
+    /// address or this contract.
     /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
     fn xfer(
         env: soroban_sdk::Env,
@@ -83,7 +370,19 @@ pub trait NonFungibleTokenTrait {
         nonce: i128,
         to: soroban_auth::Identifier,
         id: i128,
-    );
+    ) -> Result<(), Error>;
+
+    /// Transfer each token in "ids" from "from" to the identifier at the same index of "to".
+    /// Either all tokens are transferred or none is.
+    /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
+    /// for each token
+    fn xfer_batch(
+        env: soroban_sdk::Env,
+        from: soroban_auth::Signature,
+        nonce: i128,
+        to: soroban_sdk::Vec<soroban_auth::Identifier>,
+        ids: soroban_sdk::Vec<i128>,
+    ) -> Result<(), Error>;
 
     /// Transfer token "id" from "from" to "to", consuming the allowance of "spender".
     /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
@@ -94,38 +393,307 @@ pub trait NonFungibleTokenTrait {
         to: soroban_auth::Identifier,
         nonce: i128,
         id: i128,
-    );
+    ) -> Result<(), Error>;
+
+    /// Sell token "id" from "from" to "to" for "price", an amount of a token contract. The
+    /// royalty owed on token "id" is paid to its receiver and the remainder to "from", both
+    /// pulled from "to", which must have allowed this contract to spend at least "price".
+    /// Both "from" and "to" sign the same terms.
+    /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
+    fn xfer_pay(
+        env: soroban_sdk::Env,
+        from: soroban_auth::Signature,
+        from_nonce: i128,
+        to: soroban_auth::Signature,
+        to_nonce: i128,
+        id: i128,
+        price: Price,
+    ) -> Result<(), Error>;
+
+    /// Returns the price token "id" is listed for, if it is listed.
+    fn listing(env: soroban_sdk::Env, id: i128) -> Option<Price>;
+
+    /// If "owner" is the owner of token "id", list it for sale at "price", replacing any previous
+    /// listing. The listing is cancelled whenever token "id" changes owner or is burned.
+    /// Emit event with topics = ["list", owner: Identifier], data = [id: i128, price: Price]
+    fn list(
+        env: soroban_sdk::Env,
+        owner: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+        price: Price,
+    ) -> Result<(), Error>;
+
+    /// If "owner" is the owner of token "id", cancel its listing.
+    /// Emit event with topics = ["delist", owner: Identifier], data = [id: i128]
+    fn delist(
+        env: soroban_sdk::Env,
+        owner: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+    ) -> Result<(), Error>;
+
+    /// Buy token "id" at its listed "price", which "buyer" must have allowed this contract to
+    /// spend. The royalty owed on token "id" is paid to its receiver and the remainder to the
+    /// seller. Fails if token "id" is not listed or listed at a different price.
+    /// Emit event with topics = ["sale", seller: Identifier, buyer: Identifier], data = [id: i128, price: Price]
+    fn buy(
+        env: soroban_sdk::Env,
+        buyer: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+        price: Price,
+    ) -> Result<(), Error>;
+
+    /// Returns the auction of token "id", if it is being auctioned.
+    fn auction(env: soroban_sdk::Env, id: i128) -> Option<Auction>;
+
+    /// If "seller" is the owner of token "id", auction it until the ledger timestamp "end",
+    /// moving it into escrow in this contract. Bids are made in the token contract of "reserve",
+    /// the first one at least its amount and each next one at least "increment" above the last.
+    /// Emit event with topics = ["auction", seller: Identifier], data = [id: i128, end: u64]
+    fn start_auc(
+        env: soroban_sdk::Env,
+        seller: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+        reserve: Price,
+        end: u64,
+        increment: i128,
+    ) -> Result<(), Error>;
+
+    /// Bid "amount" on the auction of token "id" before it ends. The bid is held in escrow,
+    /// pulled from "bidder", which must have allowed this contract to spend it, and the previous
+    /// highest bidder is refunded.
+    /// Emit event with topics = ["bid", bidder: Identifier], data = [id: i128, amount: i128]
+    fn bid(
+        env: soroban_sdk::Env,
+        bidder: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+        amount: i128,
+    ) -> Result<(), Error>;
+
+    /// Settle the auction of token "id" once it has ended. Anyone may settle. The highest bidder
+    /// receives token "id" and the seller the highest bid, less the royalty owed on token "id".
+    /// Without bids, token "id" is returned to the seller.
+    /// Emit event with topics = ["settle", seller: Identifier, winner: Identifier], data = [id: i128, amount: i128]
+    fn settle(env: soroban_sdk::Env, id: i128) -> Result<(), Error>;
+
+    /// Transfer token "id" from "from" to "to" like "xfer". If "to" is a contract, it must
+    /// implement "on_nft_rcv(operator, from, id, data) -> Symbol" and return "on_nft_rcv" to
+    /// accept token "id", otherwise the transfer is reverted. "from" is the operator.
+    /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
+    fn safe_xfer(
+        env: soroban_sdk::Env,
+        from: soroban_auth::Signature,
+        nonce: i128,
+        to: soroban_auth::Identifier,
+        id: i128,
+        data: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
+
+    /// Transfer token "id" from "from" to "to" like "xfer_from", checking that a contract "to"
+    /// accepts it like "safe_xfer". "spender" is the operator.
+    /// Emit event with topics = ["transfer", from: Identifier, to: Identifier], data = [id: i128]
+    fn safe_from(
+        env: soroban_sdk::Env,
+        spender: soroban_auth::Signature,
+        from: soroban_auth::Identifier,
+        to: soroban_auth::Identifier,
+        nonce: i128,
+        id: i128,
+        data: soroban_sdk::Bytes,
+    ) -> Result<(), Error>;
 
-    /// If "admin" is the administrator, mint token "id" to "to".
+    /// If "minter" holds the minter role and token "id" was never minted, mint token "id" to "to".
+    /// The token gets "uri" if given, otherwise the base uri or one from the metadata pool.
+    /// If given, "royalty" overrides the collection royalty for token "id". Neither the zero
+    /// address, which marks burned tokens, nor this contract, which holds escrowed tokens, can
+    /// receive one.
     /// Emit event with topics = ["mint", to: Identifier], data = [id: i128]
     fn mint(
         env: soroban_sdk::Env,
-        admin: soroban_auth::Signature,
+        minter: soroban_auth::Signature,
         nonce: i128,
         to: soroban_auth::Identifier,
         id: i128,
-    );
+        uri: Option<soroban_sdk::Bytes>,
+        royalty: Option<Royalty>,
+    ) -> Result<(), Error>;
+
+    /// If "sigs" reach the threshold of the signer set and token "id" was never minted, mint
+    /// token "id" to "to" like "mint".
+    /// Emit event with topics = ["mint", to: Identifier], data = [id: i128]
+    fn msig_mint(
+        env: soroban_sdk::Env,
+        sigs: soroban_sdk::Vec<soroban_auth::Signature>,
+        nonce: i128,
+        to: soroban_auth::Identifier,
+        id: i128,
+        uri: Option<soroban_sdk::Bytes>,
+        royalty: Option<Royalty>,
+    ) -> Result<(), Error>;
+
+    /// If "minter" holds the minter role and none of "ids" was ever minted, mint each token in
+    /// "ids" to the identifier at the same index of "to". Either all tokens are minted or none is.
+    /// Emit event with topics = ["mint", to: Identifier], data = [id: i128] for each token
+    fn mint_batch(
+        env: soroban_sdk::Env,
+        minter: soroban_auth::Signature,
+        nonce: i128,
+        to: soroban_sdk::Vec<soroban_auth::Identifier>,
+        ids: soroban_sdk::Vec<i128>,
+    ) -> Result<(), Error>;
+
+    /// Mint the next unused token id to the invoker while the sale is open, on the same terms as
+    /// "mint_paid" but authorized by the invoking account instead of a signature.
+    /// Emit event with topics = ["mint", to: Identifier], data = [id: i128]
+    fn mint_next(env: soroban_sdk::Env) -> Result<(), Error>;
+
+    // --------------------------------------------------------------------------------
+    // Sale interface
+    // --------------------------------------------------------------------------------
+
+    /// Returns the public sale, if set.
+    fn sale(env: soroban_sdk::Env) -> Option<Sale>;
+
+    /// If "admin" is the administrator, set the public sale, replacing any previous one. Fails if
+    /// its window overlaps the presale.
+    /// Emit event with topics = ["set_sale", admin: Identifier], data = [sale: Sale]
+    fn set_sale(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        sale: Sale,
+    ) -> Result<(), Error>;
+
+    /// Returns the number of tokens "owner" minted through the public mints.
+    fn minted(env: soroban_sdk::Env, owner: soroban_auth::Identifier) -> i128;
+
+    /// Mint the next unused token id to "minter" while the sale is open, pulling the sale price
+    /// from "minter", which must have allowed this contract to spend it. Fails once "minter" has
+    /// minted the per-wallet limit or the maximum supply has been minted.
+    /// Emit event with topics = ["mint", to: Identifier], data = [id: i128]
+    fn mint_paid(
+        env: soroban_sdk::Env,
+        minter: soroban_auth::Signature,
+        nonce: i128,
+    ) -> Result<(), Error>;
+
+    /// Returns the allowlist presale, if set.
+    fn presale(env: soroban_sdk::Env) -> Option<Presale>;
+
+    /// If "admin" is the administrator, set the allowlist presale, replacing any previous one.
+    /// The presale and the public sale are open during their own ledger time windows, which must
+    /// not overlap.
+    /// Emit event with topics = ["set_pre", admin: Identifier], data = [presale: Presale]
+    fn set_pre(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        presale: Presale,
+    ) -> Result<(), Error>;
+
+    /// Returns the number of tokens "owner" minted from its presale allowance.
+    fn pre_minted(env: soroban_sdk::Env, owner: soroban_auth::Identifier) -> i128;
 
-    /// Mint the next token to "to" for demonstration.
+    /// Mint the next unused token id to "minter" while the presale is open, pulling the presale
+    /// price from "minter", which must have allowed this contract to spend it. "proof" proves
+    /// that the allowlist grants "minter" up to "allowance" tokens, where each leaf is the sha256
+    /// hash of the serialized identifier followed by the allowance as 16 big-endian bytes, and
+    /// each node hashes its two children in ascending byte order. Presale mints count towards
+    /// the maximum supply of the public sale.
     /// Emit event with topics = ["mint", to: Identifier], data = [id: i128]
-    fn mint_next(env: soroban_sdk::Env);
+    fn mint_pre(
+        env: soroban_sdk::Env,
+        minter: soroban_auth::Signature,
+        nonce: i128,
+        allowance: i128,
+        proof: soroban_sdk::Vec<soroban_sdk::BytesN<32>>,
+    ) -> Result<(), Error>;
+
+    /// Returns the sale proceeds held in the token contract "token" that were not withdrawn yet.
+    fn proceeds(env: soroban_sdk::Env, token: soroban_sdk::BytesN<32>) -> i128;
+
+    /// If "admin" is the administrator, pay out all sale proceeds held in the token contract
+    /// "token" to "to".
+    /// Emit event with topics = ["withdraw", admin: Identifier, to: Identifier], data = [token: BytesN<32>, amount: i128]
+    fn withdraw(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        token: soroban_sdk::BytesN<32>,
+        to: soroban_auth::Identifier,
+    ) -> Result<(), Error>;
 
-    /// If "admin" is the administrator or the token owner, burn token "id" from "from".
+    /// If "spender" holds the burner role, or is the owner of token "id" or an operator approved
+    /// for it, burn token "id" from its owner. Burned ids can never be minted again. Tokens in
+    /// escrow cannot be burned.
     /// Emit event with topics = ["burn", from: Identifier], data = [id: i128]
-    fn burn(env: soroban_sdk::Env, admin: soroban_auth::Signature, nonce: i128, id: i128);
+    fn burn(
+        env: soroban_sdk::Env,
+        spender: soroban_auth::Signature,
+        nonce: i128,
+        id: i128,
+    ) -> Result<(), Error>;
+
+    /// If "sigs" reach the threshold of the signer set, burn token "id" from its owner like "burn".
+    /// Emit event with topics = ["burn", from: Identifier], data = [id: i128]
+    fn msig_burn(
+        env: soroban_sdk::Env,
+        sigs: soroban_sdk::Vec<soroban_auth::Signature>,
+        nonce: i128,
+        id: i128,
+    ) -> Result<(), Error>;
+
+    /// Returns the number of tokens that have been burned.
+    fn burned(env: soroban_sdk::Env) -> i128;
+
+    // --------------------------------------------------------------------------------
+    // Royalty interface
+    // --------------------------------------------------------------------------------
+
+    /// Returns the receiver and amount of royalty owed when token "id" is sold for "sale_price",
+    /// using the royalty set at mint or the collection royalty otherwise. The amount is rounded
+    /// down and is zero, paid to the zero address, if no royalty is set. Fails if token "id"
+    /// does not exist or "sale_price" is negative.
+    fn royalty(
+        env: soroban_sdk::Env,
+        id: i128,
+        sale_price: i128,
+    ) -> Result<(soroban_auth::Identifier, i128), Error>;
+
+    /// Returns the collection royalty, if set.
+    fn get_royal(env: soroban_sdk::Env) -> Option<Royalty>;
+
+    /// If "admin" is the administrator, set the collection royalty paid on sales of tokens
+    /// without their own. Fails if "royalty" exceeds 10000 basis points.
+    /// Emit event with topics = ["set_royal", admin: Identifier], data = [receiver: Identifier, bps: u32]
+    fn set_royal(
+        env: soroban_sdk::Env,
+        admin: soroban_auth::Signature,
+        nonce: i128,
+        royalty: Royalty,
+    ) -> Result<(), Error>;
 
     // --------------------------------------------------------------------------------
     // Implementation Interface
     // --------------------------------------------------------------------------------
 
     /// Initialize the contract with "admin" as administrator, "name" as the name, and
-    /// "symbol" as the symbol.
+    /// "symbol" as the symbol. The metadata pool is seeded with the default dog images, and
+    /// "seed_hash" commits to the sha256 hash of the seed that assigns metadata from it.
     fn initialize(
         e: soroban_sdk::Env,
         admin: soroban_auth::Identifier,
         name: soroban_sdk::Bytes,
         symbol: soroban_sdk::Bytes,
-    );
+        seed_hash: soroban_sdk::BytesN<32>,
+    ) -> Result<(), Error>;
+
+    /// Returns the storage layout version the contract was initialized with.
+    fn version(e: soroban_sdk::Env) -> u32;
 }
 
 pub enum WriteType {
@@ -133,6 +701,14 @@ pub enum WriteType {
     Remove,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TokenState {
+    NotMinted,
+    Exists,
+    Escrowed,
+    Burned,
+}
+
 pub enum NftURIs {
     Bacon,
     Bailey,
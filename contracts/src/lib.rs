@@ -1,15 +1,34 @@
 #![no_std]
 
 mod admin;
+mod allowlist;
 mod approval;
+mod auction;
 mod balance;
 mod contract;
+mod enumerable;
+mod error;
 mod event;
 mod interface;
+mod market;
 mod metadata;
+mod multisig;
 mod owner;
+mod pause;
+mod payment;
+mod random;
+mod receiver;
+mod role;
+mod royalty;
+mod sale;
 mod storage_types;
 mod test;
 mod testutils;
+mod timelock;
+mod version;
 
 pub use crate::contract::NonFungibleTokenClient;
+pub use crate::error::Error;
+pub use crate::storage_types::{
+    Auction, BaseUri, Operation, Presale, Price, QueuedOp, Role, Royalty, Sale, Signer, Signers,
+};
@@ -0,0 +1,17 @@
+use crate::storage_types::{DataKey, Price};
+use soroban_sdk::Env;
+
+pub fn read_listing(env: &Env, id: i128) -> Option<Price> {
+    let key = DataKey::Listing(id);
+    env.storage().get(key).map(|price| price.unwrap())
+}
+
+pub fn write_listing(env: &Env, id: i128, price: Price) {
+    let key = DataKey::Listing(id);
+    env.storage().set(key, price);
+}
+
+pub fn remove_listing(env: &Env, id: i128) {
+    let key = DataKey::Listing(id);
+    env.storage().remove(key);
+}
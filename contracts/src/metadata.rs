@@ -1,5 +1,7 @@
-use crate::{interface::NftURIs, storage_types::DataKey};
-use soroban_sdk::{Bytes, Env};
+use crate::random::{is_revealed, rand_index, read_minter, write_minter};
+use crate::{error::Error, interface::NftURIs, storage_types::DataKey};
+use soroban_auth::Identifier;
+use soroban_sdk::{Bytes, Env, Vec};
 
 pub fn read_name(env: &Env) -> Bytes {
     let key = DataKey::Name;
@@ -21,9 +23,82 @@ pub fn write_symbol(env: &Env, symbol: Bytes) {
     env.storage().set(key, symbol)
 }
 
-pub fn read_token_uri(env: &Env, id: i128) -> Bytes {
+pub fn read_base_uri(env: &Env) -> Bytes {
+    let key = DataKey::BaseURI;
+    match env.storage().get(key) {
+        Some(base) => base.unwrap(),
+        None => Bytes::new(env),
+    }
+}
+
+pub fn write_base_uri(env: &Env, base: Bytes) {
+    let key = DataKey::BaseURI;
+    env.storage().set(key, base)
+}
+
+pub fn read_uri_suffix(env: &Env) -> Bytes {
+    let key = DataKey::URISuffix;
+    match env.storage().get(key) {
+        Some(suffix) => suffix.unwrap(),
+        None => Bytes::new(env),
+    }
+}
+
+pub fn write_uri_suffix(env: &Env, suffix: Bytes) {
+    let key = DataKey::URISuffix;
+    env.storage().set(key, suffix)
+}
+
+pub fn read_hidden_uri(env: &Env) -> Bytes {
+    let key = DataKey::HiddenURI;
+    match env.storage().get(key) {
+        Some(uri) => uri.unwrap(),
+        None => Bytes::new(env),
+    }
+}
+
+pub fn write_hidden_uri(env: &Env, uri: Bytes) {
+    let key = DataKey::HiddenURI;
+    env.storage().set(key, uri)
+}
+
+// The frontend serves the logo next to the pool images, so tokens show it until the reveal.
+pub fn write_default_hidden_uri(env: &Env) {
+    write_hidden_uri(env, to_bytes(env, "images/logo.png"))
+}
+
+// Returns the placeholder for every token until the seed is revealed, if one is set. Otherwise
+// returns the uri stored for token "id", the uri picked from the pool for tokens minted from it,
+// or composes "base uri + id + suffix".
+pub fn read_token_uri(env: &Env, id: i128) -> Result<Bytes, Error> {
+    if !is_revealed(env) {
+        let hidden = read_hidden_uri(env);
+        if !hidden.is_empty() {
+            return Ok(hidden);
+        }
+    }
+
     let key = DataKey::URI(id);
-    env.storage().get_unchecked(key).unwrap()
+    if let Some(uri) = env.storage().get(key) {
+        return Ok(uri.unwrap());
+    }
+
+    if let Some(minter) = read_minter(env, id) {
+        if !is_revealed(env) {
+            return Err(Error::NotRevealed);
+        }
+        let pool = read_uri_pool(env);
+        if pool.is_empty() {
+            return Err(Error::MetadataEmpty);
+        }
+        let index = rand_index(env, minter, id, pool.len());
+        return Ok(pool.get_unchecked(index).unwrap());
+    }
+
+    let mut uri = read_base_uri(env);
+    uri.append(&id_to_bytes(env, id));
+    uri.append(&read_uri_suffix(env));
+    Ok(uri)
 }
 
 pub fn write_token_uri(env: &Env, id: i128, uri: Bytes) {
@@ -31,22 +106,127 @@ pub fn write_token_uri(env: &Env, id: i128, uri: Bytes) {
     env.storage().set(key, uri)
 }
 
-pub fn get_rand_uri(env: &Env) -> Bytes {
-    to_bytes(
-        &env,
-        match env.ledger().timestamp() % 9 {
-            0 => NftURIs::Bacon.value(),
-            1 => NftURIs::Bailey.value(),
-            2 => NftURIs::Coco.value(),
-            3 => NftURIs::Frankie.value(),
-            4 => NftURIs::Marley.value(),
-            5 => NftURIs::Noir.value(),
-            6 => NftURIs::Riley.value(),
-            7 => NftURIs::Scout.value(),
-            8 => NftURIs::Shadow.value(),
-            _ => panic!("impossible"),
-        },
-    )
+pub fn remove_token_uri(env: &Env, id: i128) {
+    let key = DataKey::URI(id);
+    env.storage().remove(key)
+}
+
+pub fn read_uri_pool(env: &Env) -> Vec<Bytes> {
+    let key = DataKey::URIPool;
+    match env.storage().get(key) {
+        Some(pool) => pool.unwrap(),
+        None => Vec::new(env),
+    }
+}
+
+pub fn write_uri_pool(env: &Env, pool: Vec<Bytes>) {
+    let key = DataKey::URIPool;
+    env.storage().set(key, pool)
+}
+
+pub fn write_default_uri_pool(env: &Env) {
+    let mut pool = Vec::new(env);
+    for uri in [
+        NftURIs::Bacon,
+        NftURIs::Bailey,
+        NftURIs::Coco,
+        NftURIs::Frankie,
+        NftURIs::Marley,
+        NftURIs::Noir,
+        NftURIs::Riley,
+        NftURIs::Scout,
+        NftURIs::Shadow,
+    ] {
+        pool.push_back(to_bytes(env, uri.value()));
+    }
+    write_uri_pool(env, pool)
+}
+
+// The pool is frozen from the first token minted from it, as the committed seed only makes the
+// picks fair if the pool cannot be rearranged afterwards.
+pub fn is_pool_frozen(env: &Env) -> bool {
+    let key = DataKey::PoolFrozen;
+    env.storage().has(key)
+}
+
+fn freeze_pool(env: &Env) {
+    let key = DataKey::PoolFrozen;
+    env.storage().set(key, true)
+}
+
+fn check_pool_not_frozen(env: &Env) -> Result<(), Error> {
+    if is_revealed(env) {
+        return Err(Error::AlreadyRevealed);
+    }
+    if is_pool_frozen(env) {
+        return Err(Error::PoolFrozen);
+    }
+    Ok(())
+}
+
+pub fn add_pool_uri(env: &Env, uri: Bytes) -> Result<(), Error> {
+    check_pool_not_frozen(env)?;
+    let mut pool = read_uri_pool(env);
+    if pool.contains(&uri) {
+        return Err(Error::MetadataExists);
+    }
+    pool.push_back(uri);
+    write_uri_pool(env, pool);
+    Ok(())
+}
+
+pub fn remove_pool_uri(env: &Env, uri: Bytes) -> Result<(), Error> {
+    check_pool_not_frozen(env)?;
+    let mut pool = read_uri_pool(env);
+    match pool.first_index_of(&uri) {
+        Some(index) => pool.remove(index),
+        None => return Err(Error::MetadataNotFound),
+    };
+    write_uri_pool(env, pool);
+    Ok(())
+}
+
+// Assigns the uri of new token "id": "uri" if given, one picked from the pool when no base uri is
+// set, or none so that the uri is composed from the base uri.
+pub fn assign_token_uri(
+    env: &Env,
+    id: i128,
+    minter: Identifier,
+    uri: Option<Bytes>,
+) -> Result<(), Error> {
+    if let Some(uri) = uri {
+        write_token_uri(env, id, uri);
+        return Ok(());
+    }
+
+    if read_base_uri(env).is_empty() {
+        // The pick is only fair while the seed committed at initialization is still secret.
+        if is_revealed(env) {
+            return Err(Error::AlreadyRevealed);
+        }
+        if read_uri_pool(env).is_empty() {
+            return Err(Error::MetadataEmpty);
+        }
+        write_minter(env, id, minter);
+        freeze_pool(env);
+    }
+    Ok(())
+}
+
+pub fn id_to_bytes(env: &Env, id: i128) -> Bytes {
+    let mut digits = Bytes::new(env);
+    let mut value = id.unsigned_abs();
+    loop {
+        digits.insert(0, b'0' + (value % 10) as u8);
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    if id < 0 {
+        digits.insert(0, b'-');
+    }
+    digits
 }
 
 pub fn to_bytes(env: &Env, value: &str) -> Bytes {
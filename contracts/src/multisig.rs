@@ -0,0 +1,102 @@
+use crate::error::Error;
+use crate::storage_types::{DataKey, Signers};
+use soroban_auth::{verify, Identifier, Signature};
+use soroban_sdk::{Env, IntoVal, RawVal, Symbol, Vec};
+
+pub fn has_signers(env: &Env) -> bool {
+    let key = DataKey::Signers;
+    env.storage().has(key)
+}
+
+pub fn read_signers(env: &Env) -> Option<Signers> {
+    let key = DataKey::Signers;
+    env.storage().get(key).map(|signers| signers.unwrap())
+}
+
+pub fn write_signers(env: &Env, signers: Signers) {
+    let key = DataKey::Signers;
+    env.storage().set(key, signers);
+}
+
+pub fn remove_signers(env: &Env) {
+    let key = DataKey::Signers;
+    env.storage().remove(key);
+}
+
+pub fn read_signers_nonce(env: &Env) -> i128 {
+    let key = DataKey::SignNonce;
+    env.storage().get(key).unwrap_or(Ok(0)).unwrap()
+}
+
+fn write_signers_nonce(env: &Env, nonce: i128) {
+    let key = DataKey::SignNonce;
+    env.storage().set(key, nonce);
+}
+
+// A signer set needs a non-zero threshold that its distinct signers can reach.
+pub fn check_signers(signers: &Signers) -> Result<(), Error> {
+    if signers.threshold == 0 {
+        return Err(Error::InvalidSigners);
+    }
+    let mut total: u64 = 0;
+    for (i, signer) in signers.signers.iter().enumerate() {
+        let signer = signer.unwrap();
+        for other in signers.signers.iter().skip(i + 1) {
+            if other.unwrap().id == signer.id {
+                return Err(Error::DuplicateSigner);
+            }
+        }
+        total += u64::from(signer.weight);
+    }
+    if total < u64::from(signers.threshold) {
+        return Err(Error::InvalidSigners);
+    }
+    Ok(())
+}
+
+fn signer_weight(signers: &Signers, id: &Identifier) -> Option<u32> {
+    signers
+        .signers
+        .iter()
+        .map(|signer| signer.unwrap())
+        .find(|signer| signer.id == *id)
+        .map(|signer| signer.weight)
+}
+
+// Every signature signs (own id, nonce, args...) with the nonce of the signer set. Fails unless
+// the signatures come from distinct signers whose weights reach the threshold.
+pub fn verify_signers_and_consume_nonce(
+    env: &Env,
+    sigs: &Vec<Signature>,
+    nonce: i128,
+    name: Symbol,
+    args: impl IntoVal<Env, Vec<RawVal>>,
+) -> Result<(), Error> {
+    let signers = read_signers(env).ok_or(Error::NotAdmin)?;
+    if nonce != read_signers_nonce(env) {
+        return Err(Error::IncorrectNonce);
+    }
+
+    let args: Vec<RawVal> = args.into_val(env);
+    let mut seen: Vec<Identifier> = Vec::new(env);
+    let mut weight: u64 = 0;
+    for sig in sigs.iter() {
+        let sig = sig.unwrap();
+        let id = sig.identifier(env);
+        if seen.contains(&id) {
+            return Err(Error::DuplicateSigner);
+        }
+        weight += u64::from(signer_weight(&signers, &id).ok_or(Error::NotSigner)?);
+
+        let mut payload: Vec<RawVal> = (&id, nonce).into_val(env);
+        payload.append(&args);
+        verify(env, &sig, name, payload);
+        seen.push_back(id);
+    }
+    if weight < u64::from(signers.threshold) {
+        return Err(Error::BelowThreshold);
+    }
+
+    write_signers_nonce(env, nonce + 1);
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use crate::error::Error;
+use crate::interface::TokenState;
 use crate::storage_types::DataKey;
 use soroban_auth::Identifier;
 use soroban_sdk::{BytesN, Env};
@@ -6,6 +8,11 @@ pub fn zero_address(env: &Env) -> Identifier {
     Identifier::Ed25519(BytesN::from_array(env, &[0u8; 32]))
 }
 
+// Tokens held by the contract itself, such as tokens being auctioned, are in escrow.
+pub fn escrow_address(env: &Env) -> Identifier {
+    Identifier::Contract(env.current_contract())
+}
+
 pub fn read_owner(env: &Env, id: i128) -> Identifier {
     let key = DataKey::Owner(id);
     match env.storage().get(key) {
@@ -19,10 +26,62 @@ pub fn write_owner(env: &Env, id: i128, owner: Identifier) {
     env.storage().set(key, owner);
 }
 
-pub fn check_owner(env: &Env, auth: &Identifier, id: i128) {
-    assert!(
-        auth == &read_owner(env, id),
-        "not the owner for token {}",
-        id
-    );
+// Only existing tokens have an owner; the zero address that read_owner falls back to owns nothing.
+pub fn check_owner(env: &Env, auth: &Identifier, id: i128) -> Result<(), Error> {
+    match read_token_state(env, id) {
+        TokenState::Exists => {}
+        TokenState::Escrowed => return Err(Error::TokenEscrowed),
+        TokenState::NotMinted | TokenState::Burned => return Err(Error::TokenNotFound),
+    }
+    if auth != &read_owner(env, id) {
+        return Err(Error::NotOwner);
+    }
+    Ok(())
+}
+
+// The zero address marks burned tokens and the contract itself marks escrowed tokens, so neither
+// can receive one through a transfer or a mint.
+pub fn check_recipient(env: &Env, to: &Identifier) -> Result<(), Error> {
+    if *to == zero_address(env) || *to == escrow_address(env) {
+        return Err(Error::InvalidRecipient);
+    }
+    Ok(())
+}
+
+pub fn read_token_state(env: &Env, id: i128) -> TokenState {
+    let key = DataKey::Owner(id);
+    match env.storage().get::<_, Identifier>(key) {
+        Some(owner) => {
+            let owner = owner.unwrap();
+            if owner == zero_address(env) {
+                TokenState::Burned
+            } else if owner == escrow_address(env) {
+                TokenState::Escrowed
+            } else {
+                TokenState::Exists
+            }
+        }
+        None => TokenState::NotMinted,
+    }
+}
+
+pub fn check_exists(env: &Env, id: i128) -> Result<(), Error> {
+    match read_token_state(env, id) {
+        TokenState::Exists | TokenState::Escrowed => Ok(()),
+        TokenState::NotMinted | TokenState::Burned => Err(Error::TokenNotFound),
+    }
+}
+
+pub fn check_not_escrowed(env: &Env, id: i128) -> Result<(), Error> {
+    if read_token_state(env, id) == TokenState::Escrowed {
+        return Err(Error::TokenEscrowed);
+    }
+    Ok(())
+}
+
+pub fn check_not_minted(env: &Env, id: i128) -> Result<(), Error> {
+    if read_token_state(env, id) != TokenState::NotMinted {
+        return Err(Error::TokenExists);
+    }
+    Ok(())
 }
@@ -0,0 +1,20 @@
+use crate::error::Error;
+use crate::storage_types::DataKey;
+use soroban_sdk::Env;
+
+pub fn is_paused(env: &Env) -> bool {
+    let key = DataKey::Paused;
+    env.storage().get(key).unwrap_or(Ok(false)).unwrap()
+}
+
+pub fn write_paused(env: &Env, paused: bool) {
+    let key = DataKey::Paused;
+    env.storage().set(key, paused);
+}
+
+pub fn check_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::Paused);
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::error::Error;
+use crate::owner::escrow_address;
+use crate::royalty::royalty_info;
+use crate::storage_types::Price;
+use soroban_auth::{Identifier, Signature};
+use soroban_sdk::{contractclient, BytesN, Env};
+
+// The subset of the standard token interface used to collect payments.
+#[allow(dead_code)]
+#[contractclient(name = "TokenClient")]
+pub trait TokenInterface {
+    fn xfer(env: Env, from: Signature, nonce: i128, to: Identifier, amount: i128);
+
+    fn xfer_from(
+        env: Env,
+        spender: Signature,
+        nonce: i128,
+        from: Identifier,
+        to: Identifier,
+        amount: i128,
+    );
+}
+
+// Pulls "price" from "buyer" using the allowance granted to this contract, paying the royalty owed
+// on token "id" to its receiver and the remainder to "seller".
+pub fn pay_with_royalty(
+    env: &Env,
+    price: &Price,
+    buyer: &Identifier,
+    seller: &Identifier,
+    id: i128,
+) -> Result<(), Error> {
+    let client = TokenClient::new(env, &price.token);
+    split_payment(env, price, seller, id, |to, amount| {
+        client.xfer_from(&Signature::Invoker, &0, buyer, to, amount)
+    })
+}
+
+// Pays "price" held in escrow by this contract, the royalty owed on token "id" to its receiver
+// and the remainder to "seller".
+pub fn pay_from_escrow(
+    env: &Env,
+    price: &Price,
+    seller: &Identifier,
+    id: i128,
+) -> Result<(), Error> {
+    let client = TokenClient::new(env, &price.token);
+    split_payment(env, price, seller, id, |to, amount| {
+        client.xfer(&Signature::Invoker, &0, to, amount)
+    })
+}
+
+// Pulls "amount" of "token" from "from" into escrow using the allowance granted to this contract.
+pub fn deposit(env: &Env, token: &BytesN<32>, from: &Identifier, amount: i128) {
+    TokenClient::new(env, token).xfer_from(
+        &Signature::Invoker,
+        &0,
+        from,
+        &escrow_address(env),
+        &amount,
+    );
+}
+
+// Returns "amount" of "token" held in escrow to "to".
+pub fn refund(env: &Env, token: &BytesN<32>, to: &Identifier, amount: i128) {
+    TokenClient::new(env, token).xfer(&Signature::Invoker, &0, to, &amount);
+}
+
+fn split_payment(
+    env: &Env,
+    price: &Price,
+    seller: &Identifier,
+    id: i128,
+    send: impl Fn(&Identifier, &i128),
+) -> Result<(), Error> {
+    if price.amount < 0 {
+        return Err(Error::NegativeAmount);
+    }
+    let (receiver, royalty) = royalty_info(env, id, price.amount)?;
+
+    if royalty > 0 {
+        send(&receiver, &royalty);
+    }
+    if price.amount - royalty > 0 {
+        send(seller, &(price.amount - royalty));
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::error::Error;
+use crate::storage_types::DataKey;
+use soroban_auth::Identifier;
+use soroban_sdk::{serde::Serialize, Bytes, BytesN, Env};
+
+pub fn read_seed_hash(env: &Env) -> BytesN<32> {
+    let key = DataKey::SeedHash;
+    env.storage().get_unchecked(key).unwrap()
+}
+
+pub fn write_seed_hash(env: &Env, hash: BytesN<32>) {
+    let key = DataKey::SeedHash;
+    env.storage().set(key, hash);
+}
+
+pub fn is_revealed(env: &Env) -> bool {
+    let key = DataKey::Seed;
+    env.storage().has(key)
+}
+
+pub fn read_seed(env: &Env) -> Bytes {
+    let key = DataKey::Seed;
+    env.storage().get_unchecked(key).unwrap()
+}
+
+pub fn write_seed(env: &Env, seed: Bytes) -> Result<(), Error> {
+    if is_revealed(env) {
+        return Err(Error::AlreadyRevealed);
+    }
+
+    if env.crypto().sha256(&seed) != read_seed_hash(env) {
+        return Err(Error::InvalidSeed);
+    }
+
+    env.storage().set(DataKey::Seed, seed);
+    Ok(())
+}
+
+pub fn read_minter(env: &Env, id: i128) -> Option<Identifier> {
+    let key = DataKey::Minter(id);
+    env.storage().get(key).map(|minter| minter.unwrap())
+}
+
+pub fn write_minter(env: &Env, id: i128, minter: Identifier) {
+    let key = DataKey::Minter(id);
+    env.storage().set(key, minter);
+}
+
+pub fn remove_minter(env: &Env, id: i128) {
+    let key = DataKey::Minter(id);
+    env.storage().remove(key);
+}
+
+// Derives an index below "len" from the revealed seed, "minter" and "id". The seed is committed
+// before minting and revealed after, so neither the minter nor the ledger time can steer it.
+pub fn rand_index(env: &Env, minter: Identifier, id: i128, len: u32) -> u32 {
+    let mut data = read_seed(env);
+    data.append(&minter.serialize(env));
+    data.extend_from_array(&id.to_be_bytes());
+    let hash = env.crypto().sha256(&data).to_array();
+
+    // Reducing 128 bits keeps the modulo bias negligible for any pool length.
+    let mut value = [0u8; 16];
+    value.copy_from_slice(&hash[..16]);
+    (u128::from_be_bytes(value) % len as u128) as u32
+}
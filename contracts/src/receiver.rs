@@ -0,0 +1,37 @@
+use crate::error::Error;
+use soroban_auth::Identifier;
+use soroban_sdk::{contractclient, symbol, Bytes, Env, Symbol};
+
+// Receiver contracts return the name of the hook to acknowledge that they can handle the token.
+pub const RECEIVED: Symbol = symbol!("on_nft_rcv");
+
+// The hook contracts implement to accept tokens sent with a safe transfer.
+#[allow(dead_code)]
+#[contractclient(name = "ReceiverClient")]
+pub trait ReceiverInterface {
+    fn on_nft_rcv(
+        env: Env,
+        operator: Identifier,
+        from: Identifier,
+        id: i128,
+        data: Bytes,
+    ) -> Symbol;
+}
+
+// If "to" is a contract, calls its hook and fails unless it acknowledges receiving token "id".
+pub fn check_received(
+    env: &Env,
+    operator: &Identifier,
+    from: &Identifier,
+    to: &Identifier,
+    id: i128,
+    data: &Bytes,
+) -> Result<(), Error> {
+    if let Identifier::Contract(contract_id) = to {
+        let response = ReceiverClient::new(env, contract_id).on_nft_rcv(operator, from, &id, data);
+        if response != RECEIVED {
+            return Err(Error::NotReceived);
+        }
+    }
+    Ok(())
+}
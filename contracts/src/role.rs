@@ -0,0 +1,41 @@
+use crate::admin::is_admin;
+use crate::error::Error;
+use crate::storage_types::{DataKey, Role, RoleKey};
+use soroban_auth::{Identifier, Signature};
+use soroban_sdk::Env;
+
+// The administrator implicitly holds every role, and is the only holder of the admin role.
+pub fn has_role(env: &Env, role: Role, account: &Identifier) -> bool {
+    if is_admin(env, account) {
+        return true;
+    }
+    let key = DataKey::Role(RoleKey {
+        role,
+        account: account.clone(),
+    });
+    env.storage().get(key).unwrap_or(Ok(false)).unwrap()
+}
+
+pub fn write_role(env: &Env, role: Role, account: Identifier, granted: bool) {
+    let key = DataKey::Role(RoleKey { role, account });
+    if granted {
+        env.storage().set(key, true);
+    } else {
+        env.storage().remove(key);
+    }
+}
+
+pub fn check_role(env: &Env, auth: &Signature, role: Role) -> Result<(), Error> {
+    if !has_role(env, role, &auth.identifier(env)) {
+        return Err(Error::MissingRole);
+    }
+    Ok(())
+}
+
+// The admin role changes hands with "accept_adm" or "msig_admin" only.
+pub fn check_grantable(role: &Role) -> Result<(), Error> {
+    if *role == Role::Admin {
+        return Err(Error::InvalidRole);
+    }
+    Ok(())
+}
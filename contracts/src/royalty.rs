@@ -0,0 +1,61 @@
+use crate::error::Error;
+use crate::owner::zero_address;
+use crate::storage_types::{DataKey, Royalty};
+use soroban_auth::Identifier;
+use soroban_sdk::Env;
+
+// Royalties are expressed in basis points of the sale price.
+pub const MAX_BPS: u32 = 10_000;
+
+pub fn check_royalty(royalty: &Royalty) -> Result<(), Error> {
+    if royalty.bps > MAX_BPS {
+        return Err(Error::InvalidRoyalty);
+    }
+    Ok(())
+}
+
+pub fn read_royalty(env: &Env) -> Option<Royalty> {
+    let key = DataKey::Royalty;
+    env.storage().get(key).map(|royalty| royalty.unwrap())
+}
+
+pub fn write_royalty(env: &Env, royalty: Royalty) {
+    let key = DataKey::Royalty;
+    env.storage().set(key, royalty);
+}
+
+pub fn read_token_royalty(env: &Env, id: i128) -> Option<Royalty> {
+    let key = DataKey::RoyaltyOf(id);
+    env.storage().get(key).map(|royalty| royalty.unwrap())
+}
+
+pub fn write_token_royalty(env: &Env, id: i128, royalty: Royalty) {
+    let key = DataKey::RoyaltyOf(id);
+    env.storage().set(key, royalty);
+}
+
+pub fn remove_token_royalty(env: &Env, id: i128) {
+    let key = DataKey::RoyaltyOf(id);
+    env.storage().remove(key);
+}
+
+// Returns the receiver and amount owed for selling token "id" at "sale_price", preferring the
+// token override over the collection default. Without either, nothing is owed to the zero address.
+pub fn royalty_info(env: &Env, id: i128, sale_price: i128) -> Result<(Identifier, i128), Error> {
+    if sale_price < 0 {
+        return Err(Error::NegativeAmount);
+    }
+
+    match read_token_royalty(env, id).or_else(|| read_royalty(env)) {
+        Some(royalty) => Ok((royalty.receiver, royalty_amount(sale_price, royalty.bps))),
+        None => Ok((zero_address(env), 0)),
+    }
+}
+
+// Computes "sale_price * bps / MAX_BPS" rounded down. Splitting the price around MAX_BPS keeps
+// every intermediate product below "sale_price", so it cannot overflow for any i128 price.
+pub fn royalty_amount(sale_price: i128, bps: u32) -> i128 {
+    let bps = bps as i128;
+    let max = MAX_BPS as i128;
+    (sale_price / max) * bps + (sale_price % max) * bps / max
+}
@@ -0,0 +1,89 @@
+use crate::allowlist::read_presale;
+use crate::error::Error;
+use crate::payment::deposit;
+use crate::storage_types::{DataKey, Presale, Price, Sale};
+use soroban_auth::Identifier;
+use soroban_sdk::{BytesN, Env};
+
+pub fn read_sale(env: &Env) -> Option<Sale> {
+    let key = DataKey::Sale;
+    env.storage().get(key).map(|sale| sale.unwrap())
+}
+
+pub fn write_sale(env: &Env, sale: Sale) {
+    let key = DataKey::Sale;
+    env.storage().set(key, sale);
+}
+
+pub fn check_sale(env: &Env, sale: &Sale) -> Result<(), Error> {
+    if sale.price.amount < 0 || sale.limit <= 0 || sale.max_supply < 0 || sale.start >= sale.end {
+        return Err(Error::InvalidSale);
+    }
+    match read_presale(env) {
+        Some(presale) => check_windows(sale, &presale),
+        None => Ok(()),
+    }
+}
+
+pub fn check_presale(env: &Env, presale: &Presale) -> Result<(), Error> {
+    if presale.price.amount < 0 || presale.start >= presale.end {
+        return Err(Error::InvalidSale);
+    }
+    match read_sale(env) {
+        Some(sale) => check_windows(&sale, presale),
+        None => Ok(()),
+    }
+}
+
+// The presale and the public sale are never open at the same time, so that allowlisted wallets
+// cannot mint at both prices at once.
+fn check_windows(sale: &Sale, presale: &Presale) -> Result<(), Error> {
+    if presale.start < sale.end && sale.start < presale.end {
+        return Err(Error::SaleOverlap);
+    }
+    Ok(())
+}
+
+// Returns the presale if the ledger time is within its window.
+pub fn read_active_presale(env: &Env) -> Result<Presale, Error> {
+    let presale = read_presale(env).ok_or(Error::SaleNotActive)?;
+    let now = env.ledger().timestamp();
+    if now < presale.start || now >= presale.end {
+        return Err(Error::SaleNotActive);
+    }
+    Ok(presale)
+}
+
+// Pulls "price" from "from" into escrow and books it as proceeds.
+pub fn collect_proceeds(env: &Env, price: Price, from: &Identifier) {
+    if price.amount > 0 {
+        deposit(env, &price.token, from, price.amount);
+        let proceeds = read_proceeds(env, price.token.clone());
+        write_proceeds(env, price.token, proceeds + price.amount);
+    }
+}
+
+// Returns the sale if the ledger time is within its window.
+pub fn read_active_sale(env: &Env) -> Result<Sale, Error> {
+    let sale = read_sale(env).ok_or(Error::SaleNotActive)?;
+    let now = env.ledger().timestamp();
+    if now < sale.start || now >= sale.end {
+        return Err(Error::SaleNotActive);
+    }
+    Ok(sale)
+}
+
+// Proceeds are tracked per payment token so that withdrawals never touch funds held in escrow for
+// other purposes, such as auction bids.
+pub fn read_proceeds(env: &Env, token: BytesN<32>) -> i128 {
+    let key = DataKey::Proceeds(token);
+    match env.storage().get(key) {
+        Some(proceeds) => proceeds.unwrap(),
+        None => 0,
+    }
+}
+
+pub fn write_proceeds(env: &Env, token: BytesN<32>, amount: i128) {
+    let key = DataKey::Proceeds(token);
+    env.storage().set(key, amount);
+}
@@ -1,5 +1,5 @@
 use soroban_auth::Identifier;
-use soroban_sdk::contracttype;
+use soroban_sdk::{contracttype, Bytes, BytesN, Vec};
 
 #[derive(Clone)]
 #[contracttype]
@@ -15,6 +15,139 @@ pub enum ApprovalKey {
     ID(i128),
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Role {
+    Admin,
+    Minter,
+    Burner,
+    Metadata,
+    Pauser,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RoleKey {
+    pub role: Role,
+    pub account: Identifier,
+}
+
+// "expiry" is the ledger timestamp from which the approval no longer applies, u64::MAX if never.
+#[derive(Clone)]
+#[contracttype]
+pub struct TokenApproval {
+    pub operator: Identifier,
+    pub expiry: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Signer {
+    pub id: Identifier,
+    pub weight: u32,
+}
+
+// Admin actions need signatures from distinct "signers" whose weights add up to "threshold".
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Signers {
+    pub signers: Vec<Signer>,
+    pub threshold: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct BaseUri {
+    pub base: Bytes,
+    pub suffix: Bytes,
+}
+
+// Admin actions that can be queued behind the timelock. There is no upgrade operation, as the
+// host offers contracts no way to replace their own code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum Operation {
+    // Proposes the administrator, who accepts with "accept_adm".
+    SetAdmin(Identifier),
+    SetBase(BaseUri),
+    SetRoyal(Royalty),
+    SetDelay(u64),
+}
+
+// "eta" is the ledger timestamp from which the operation can be executed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct QueuedOp {
+    pub id: u32,
+    pub op: Operation,
+    pub eta: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Royalty {
+    pub receiver: Identifier,
+    pub bps: u32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Price {
+    pub token: BytesN<32>,
+    pub amount: i128,
+}
+
+// "bidder" is the zero address until the first bid.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Auction {
+    pub seller: Identifier,
+    pub reserve: Price,
+    pub end: u64,
+    pub increment: i128,
+    pub bidder: Identifier,
+    pub bid: i128,
+}
+
+// Public sale of at most "limit" tokens per wallet for "price" each, open from the ledger timestamp
+// "start" until "end", while fewer than "max_supply" tokens have ever been minted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Sale {
+    pub price: Price,
+    pub limit: i128,
+    pub max_supply: i128,
+    pub start: u64,
+    pub end: u64,
+}
+
+// Presale for "price" each, open from the ledger timestamp "start" until "end" to the allowlist
+// whose Merkle root is "root". Its leaves hash an identifier with the number of tokens it may mint.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Presale {
+    pub root: BytesN<32>,
+    pub price: Price,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct OwnerIndex {
+    pub owner: Identifier,
+    pub index: i128,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum IndexKey {
+    Token(i128),
+    TokenOf(i128),
+    Owner(OwnerIndex),
+    OwnerOf(i128),
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -28,4 +161,33 @@ pub enum DataKey {
     Approval(ApprovalKey),
     Owner(i128),
     Supply,
+    Index(IndexKey),
+    NextId,
+    Burned,
+    URIPool,
+    BaseURI,
+    URISuffix,
+    HiddenURI,
+    SeedHash,
+    Seed,
+    Minter(i128),
+    Royalty,
+    RoyaltyOf(i128),
+    Listing(i128),
+    Auction(i128),
+    Sale,
+    Proceeds(BytesN<32>),
+    Presale,
+    PreMinted(Identifier),
+    Paused,
+    Role(RoleKey),
+    PendAdmin,
+    Renounced,
+    Signers,
+    SignNonce,
+    Delay,
+    Queue,
+    NextOpId,
+    Version,
+    PoolFrozen,
 }
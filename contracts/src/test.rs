@@ -1,16 +1,57 @@
 #![cfg(test)]
+use crate::allowlist::{leaf_hash, node_hash};
+use crate::approval::{read_approval, write_approval};
+use crate::error::Error;
 use crate::interface::NftURIs;
+use crate::market::write_listing;
 use crate::metadata::to_bytes;
 use crate::owner::zero_address;
-use crate::testutils::{to_ed25519, Token, TOKEN_NAME, TOKEN_SYMBOL};
+use crate::random::{rand_index, write_seed, write_seed_hash};
+use crate::royalty::{royalty_amount, MAX_BPS};
+use crate::storage_types::{
+    BaseUri, Operation, Presale, Price, QueuedOp, Role, Royalty, Sale, Signer, Signers,
+};
+use crate::testutils::{
+    forge_zero_key, register_contract, sign, to_ed25519, PaymentToken, Receiver, ReceiverClient,
+    Token, TOKEN_NAME, TOKEN_SEED, TOKEN_SYMBOL,
+};
+use crate::timelock::MAX_DELAY;
+use crate::version::STORAGE_VERSION;
 use ed25519_dalek::Keypair;
 use rand::thread_rng;
-use soroban_sdk::testutils::Accounts;
+use soroban_auth::{Identifier, Signature};
+use soroban_sdk::testutils::{Accounts, Events, Ledger};
+use soroban_sdk::{symbol, vec, Bytes, BytesN, Env, IntoVal, RawVal, TryFromVal, Vec};
 
 fn generate_keypair() -> Keypair {
     Keypair::generate(&mut thread_rng())
 }
 
+fn forge_signature(signature: Signature, victim: &Identifier) -> Signature {
+    match (signature, victim) {
+        (Signature::Ed25519(mut sig), Identifier::Ed25519(public_key)) => {
+            sig.public_key = public_key.clone();
+            Signature::Ed25519(sig)
+        }
+        _ => panic!("not an ed25519 signature"),
+    }
+}
+
+// Opens a free public sale for "mint_next", one token per wallet.
+fn open_free_sale(env: &Env, token: &Token, admin: &Keypair) {
+    let sale = Sale {
+        price: Price {
+            token: BytesN::from_array(env, &[0; 32]),
+            amount: 0,
+        },
+        limit: 1,
+        max_supply: 100,
+        start: 0,
+        end: u64::MAX,
+    };
+    token.set_sale(admin, &sale);
+}
+
 #[test]
 fn test_mint() {
     let (env, token) = Token::create();
@@ -29,6 +70,7 @@ fn test_mint() {
     assert_eq!(token.nonce(&admin_id), 1);
     assert_eq!(token.owner(&1), user_id);
 
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
     let uri = token.token_uri(&1);
     assert!(
         uri == to_bytes(&env, NftURIs::Bacon.value())
@@ -51,6 +93,7 @@ fn test_mint_next() {
     let admin_id = to_ed25519(&env, &admin);
 
     token.initialize(&admin_id);
+    open_free_sale(&env, &token, &admin);
 
     let user1 = env.accounts().generate();
     token.mint_next(&user1);
@@ -64,7 +107,6 @@ fn test_mint_next() {
 }
 
 #[test]
-#[should_panic(expected = "already minted")]
 fn test_mint_next_twice() {
     let (env, token) = Token::create();
 
@@ -72,14 +114,64 @@ fn test_mint_next_twice() {
     let admin_id = to_ed25519(&env, &admin);
 
     token.initialize(&admin_id);
+    open_free_sale(&env, &token, &admin);
 
     let user1 = env.accounts().generate();
     token.mint_next(&user1);
     assert_eq!(token.balance(&(&user1).into()), 1);
     assert_eq!(token.owner(&1), (&user1).into());
 
+    assert_eq!(
+        token.client().with_source_account(&user1).try_mint_next(),
+        Err(Ok(Error::AlreadyMinted))
+    );
+}
+
+#[test]
+fn test_mint_next_sale() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+
+    token.initialize(&admin_id);
+
+    let user1 = env.accounts().generate();
+    assert_eq!(
+        token.client().with_source_account(&user1).try_mint_next(),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    // The administrator's token counts towards the maximum supply of the sale.
+    token.mint(&admin, &admin_id, &1);
+    token.set_sale(
+        &admin,
+        &Sale {
+            price: Price {
+                token: BytesN::from_array(&env, &[0; 32]),
+                amount: 0,
+            },
+            limit: 1,
+            max_supply: 2,
+            start: 100,
+            end: 200,
+        },
+    );
+    assert_eq!(
+        token.client().with_source_account(&user1).try_mint_next(),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    set_timestamp(&env, 100);
     token.mint_next(&user1);
-    token.mint_next(&user1);
+    assert_eq!(token.owner(&2), (&user1).into());
+    assert_eq!(token.client().minted(&(&user1).into()), 1);
+
+    let user2 = env.accounts().generate();
+    assert_eq!(
+        token.client().with_source_account(&user2).try_mint_next(),
+        Err(Ok(Error::SoldOut))
+    );
 }
 
 #[test]
@@ -100,7 +192,7 @@ fn test_burn() {
 
     token.burn(&admin, &1);
     assert_eq!(token.balance(&user_id), 0);
-    assert_eq!(token.owner(&1), zero_address(&env));
+    assert_eq!(token.client().try_owner(&1), Err(Ok(Error::TokenNotFound)));
 }
 
 #[test]
@@ -130,7 +222,104 @@ fn test_xfer() {
 }
 
 #[test]
-#[should_panic(expected = "not the owner for token 2")]
+fn test_xfer_to_self() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+    token.mint(&admin, &user1_id, &2);
+
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer"), (&user1_id, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &user1_id, &1),
+        Err(Ok(Error::SelfTransfer))
+    );
+
+    token.appr_all(&user1, &user1_id, &true);
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer_from"), (&user1_id, &user1_id, &1i128));
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user1_id, &user1_id, &nonce, &1),
+        Err(Ok(Error::SelfTransfer))
+    );
+
+    // The owner index is intact, so both tokens can still move.
+    assert_eq!(token.token_of(&user1_id, &0), 1);
+    assert_eq!(token.token_of(&user1_id, &1), 2);
+    token.xfer(&user1, &user2_id, &2);
+    token.xfer(&user1, &user2_id, &1);
+    assert_eq!(token.balance(&user2_id), 2);
+}
+
+#[test]
+fn test_xfer_to_zero_address() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let zero = zero_address(&env);
+
+    token.initialize(&admin_id);
+
+    let (auth, nonce) = token.auth(&admin, symbol!("mint"), (&zero, &1i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &zero, &1, &None, &None),
+        Err(Ok(Error::InvalidRecipient))
+    );
+
+    token.mint(&admin, &user_id, &1);
+    let (auth, nonce) = token.auth(&user, symbol!("xfer"), (&zero, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &zero, &1),
+        Err(Ok(Error::InvalidRecipient))
+    );
+    assert_eq!(token.owner(&1), user_id);
+    assert_eq!(token.client().supply(), 1);
+    assert_eq!(token.burned(), 0);
+}
+
+#[test]
+fn test_xfer_to_contract() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let escrow = Identifier::Contract(token.contract_id());
+
+    token.initialize(&admin_id);
+
+    // Tokens owned by the contract count as escrowed, so only auctions may move them there.
+    let (auth, nonce) = token.auth(&admin, symbol!("mint"), (&escrow, &1i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &escrow, &1, &None, &None),
+        Err(Ok(Error::InvalidRecipient))
+    );
+
+    token.mint(&admin, &user_id, &1);
+    let (auth, nonce) = token.auth(&user, symbol!("xfer"), (&escrow, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &escrow, &1),
+        Err(Ok(Error::InvalidRecipient))
+    );
+    assert_eq!(token.owner(&1), user_id);
+}
+
+#[test]
 fn test_xfer_non_owner() {
     let (env, token) = Token::create();
 
@@ -148,7 +337,17 @@ fn test_xfer_non_owner() {
     assert_eq!(token.nonce(&admin_id), 1);
     assert_eq!(token.owner(&1), user1_id);
 
-    token.xfer(&user1, &user2_id, &2);
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer"), (&user2_id, &2i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &user2_id, &2),
+        Err(Ok(Error::TokenNotFound))
+    );
+
+    let (auth, nonce) = token.auth(&user2, symbol!("xfer"), (&user2_id, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &user2_id, &1),
+        Err(Ok(Error::NotOwner))
+    );
 }
 
 #[test]
@@ -202,7 +401,7 @@ fn test_xfer_from_appr_all() {
     assert_eq!(token.nonce(&admin_id), 1);
     assert_eq!(token.owner(&1), user1_id);
 
-    token.appr_all(&user1, &user3_id);
+    token.appr_all(&user1, &user3_id, &true);
     assert!(token.is_appr(&user1_id, &user3_id));
 
     token.xfer_from(&user3, &user1_id, &user2_id, &1);
@@ -214,7 +413,6 @@ fn test_xfer_from_appr_all() {
 }
 
 #[test]
-#[should_panic(expected = "not approved")]
 fn test_xfer_from_non_approved() {
     let (env, token) = Token::create();
 
@@ -233,11 +431,16 @@ fn test_xfer_from_non_approved() {
     assert_eq!(token.nonce(&admin1_id), 1);
     assert_eq!(token.owner(&1), user1_id);
 
-    token.xfer_from(&user3, &user1_id, &user2_id, &1);
+    let (auth, nonce) = token.auth(&user3, symbol!("xfer_from"), (&user1_id, &user2_id, &1i128));
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user1_id, &user2_id, &nonce, &1),
+        Err(Ok(Error::NotApproved))
+    );
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
 fn test_initialize_already_initialized() {
     let (env, token) = Token::create();
 
@@ -245,20 +448,3161 @@ fn test_initialize_already_initialized() {
     let admin1_id = to_ed25519(&env, &admin1);
 
     token.initialize(&admin1_id);
-    token.initialize(&admin1_id);
+    assert_eq!(
+        token.client().try_initialize(
+            &admin1_id,
+            &to_bytes(&env, TOKEN_NAME),
+            &to_bytes(&env, TOKEN_SYMBOL),
+            &env.crypto().sha256(&to_bytes(&env, TOKEN_SEED))
+        ),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_version() {
+    let (env, token) = Token::create();
+
+    let admin_id = to_ed25519(&env, &generate_keypair());
+    assert_eq!(token.client().version(), 0);
+
+    token.initialize(&admin_id);
+    assert_eq!(token.client().version(), STORAGE_VERSION);
+}
+
+#[test]
+fn test_prop_admin_bad_actor() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let user = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+
+    let (auth, nonce) = token.auth(&user, symbol!("prop_admin"), (&user_id,));
+    assert_eq!(
+        token.client().try_prop_admin(&auth, &nonce, &user_id),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_xfer_forged_signature() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let attacker = generate_keypair();
+    let attacker_id = to_ed25519(&env, &attacker);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    // The attacker signs the payload with their own key but claims to be the owner.
+    let nonce = token.nonce(&user_id);
+    let auth = sign(
+        &env,
+        &contract_id,
+        &attacker,
+        symbol!("xfer"),
+        (&user_id, &nonce, &attacker_id, &1i128),
+    );
+    let auth = forge_signature(auth, &user_id);
+
+    token.client().xfer(&auth, &nonce, &attacker_id, &1);
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_mint_forged_signature() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let attacker = generate_keypair();
+    let attacker_id = to_ed25519(&env, &attacker);
+
+    token.initialize(&admin_id);
+
+    let nonce = token.nonce(&admin_id);
+    let auth = sign(
+        &env,
+        &contract_id,
+        &attacker,
+        symbol!("mint"),
+        (
+            &admin_id,
+            &nonce,
+            &attacker_id,
+            &1i128,
+            None::<Bytes>,
+            None::<Royalty>,
+        ),
+    );
+    let auth = forge_signature(auth, &admin_id);
+
+    token
+        .client()
+        .mint(&auth, &nonce, &attacker_id, &1, &None, &None);
+}
+
+#[test]
+fn test_xfer_from_zero_key() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user_id = to_ed25519(&env, &generate_keypair());
+    let thief_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    for id in 1..=8 {
+        token.mint(&admin, &user_id, &id);
+    }
+
+    // Signatures for the zero address verify often enough to find one among a few tokens.
+    let (id, auth) = (1..=8i128)
+        .find_map(|id| {
+            let args = (&user_id, &thief_id, id);
+            forge_zero_key(&env, &token.contract_id(), symbol!("xfer_from"), args).map(|a| (id, a))
+        })
+        .expect("no forgeable payload");
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user_id, &thief_id, &0, &id),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    let data = Bytes::new(&env);
+    let (id, auth) = (1..=8i128)
+        .find_map(|id| {
+            let args = (&user_id, &thief_id, id, &data);
+            forge_zero_key(&env, &token.contract_id(), symbol!("safe_from"), args).map(|a| (id, a))
+        })
+        .expect("no forgeable payload");
+    assert_eq!(
+        token
+            .client()
+            .try_safe_from(&auth, &user_id, &thief_id, &0, &id, &data),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(token.balance(&user_id), 8);
+}
+
+#[test]
+fn test_burn_zero_key() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    for id in 1..=8 {
+        token.mint(&admin, &user_id, &id);
+    }
+
+    // Tokens without an approval must not count as approved to the zero address.
+    let (id, auth) = (1..=8i128)
+        .find_map(|id| {
+            forge_zero_key(&env, &token.contract_id(), symbol!("burn"), (id,)).map(|a| (id, a))
+        })
+        .expect("no forgeable payload");
+    assert_eq!(
+        token.client().try_burn(&auth, &0, &id),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(token.supply(), 8);
+    assert_eq!(token.burned(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_xfer_tampered_args() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+    let attacker_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+
+    // A valid signature for a transfer to "user2" cannot be redirected to another recipient.
+    let nonce = token.nonce(&user1_id);
+    let auth = sign(
+        &env,
+        &contract_id,
+        &user1,
+        symbol!("xfer"),
+        (&user1_id, &nonce, &user2_id, &1i128),
+    );
+
+    token.client().xfer(&auth, &nonce, &attacker_id, &1);
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_appr_all_tampered_args() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let operator_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+
+    let nonce = token.nonce(&user_id);
+    let auth = sign(
+        &env,
+        &contract_id,
+        &user,
+        symbol!("appr_all"),
+        (&user_id, &nonce, &operator_id, false, ()),
+    );
+
+    token
+        .client()
+        .appr_all(&auth, &nonce, &operator_id, &true, &None);
+}
+
+#[test]
+fn test_appr_replayed_signature() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let operator_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    let nonce = token.nonce(&user_id);
+    let auth = sign(
+        &env,
+        &contract_id,
+        &user,
+        symbol!("appr"),
+        (&user_id, &nonce, &operator_id, &1i128, ()),
+    );
+
+    token.client().appr(&auth, &nonce, &operator_id, &1, &None);
+    assert_eq!(token.get_appr(&1), operator_id);
+
+    assert_eq!(
+        token
+            .client()
+            .try_appr(&auth, &nonce, &operator_id, &1, &None),
+        Err(Ok(Error::IncorrectNonce))
+    );
+}
+
+#[test]
+fn test_enumerable() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+
+    token.initialize(&admin_id);
+    assert_eq!(token.supply(), 0);
+
+    token.mint(&admin, &user1_id, &1);
+    token.mint(&admin, &user1_id, &2);
+    token.mint(&admin, &user2_id, &3);
+    assert_eq!(token.supply(), 3);
+    assert_eq!(token.token_at(&0), 1);
+    assert_eq!(token.token_at(&1), 2);
+    assert_eq!(token.token_at(&2), 3);
+    assert_eq!(token.token_of(&user1_id, &0), 1);
+    assert_eq!(token.token_of(&user1_id, &1), 2);
+    assert_eq!(token.token_of(&user2_id, &0), 3);
+
+    token.xfer(&user1, &user2_id, &1);
+    assert_eq!(token.balance(&user1_id), 1);
+    assert_eq!(token.balance(&user2_id), 2);
+    assert_eq!(token.token_of(&user1_id, &0), 2);
+    assert_eq!(token.token_of(&user2_id, &0), 3);
+    assert_eq!(token.token_of(&user2_id, &1), 1);
+
+    token.burn(&admin, &3);
+    assert_eq!(token.supply(), 2);
+    assert_eq!(token.token_at(&0), 1);
+    assert_eq!(token.token_at(&1), 2);
+    assert_eq!(token.balance(&user2_id), 1);
+    assert_eq!(token.token_of(&user2_id, &0), 1);
+
+    open_free_sale(&env, &token, &admin);
+    let user3 = env.accounts().generate();
+    token.mint_next(&user3);
+    assert_eq!(token.supply(), 3);
+    assert_eq!(token.token_at(&2), 4);
+    assert_eq!(token.owner(&4), (&user3).into());
+    assert_eq!(token.owner(&1), user2_id);
+    assert_eq!(token.token_of(&(&user3).into(), &0), 4);
 }
 
 #[test]
-#[should_panic(expected = "not authorized by admin")]
-fn test_set_admin_bad_actor() {
+fn test_token_of_out_of_bounds() {
     let (env, token) = Token::create();
 
     let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
     let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    assert_eq!(
+        token.client().try_token_of(&user_id, &1),
+        Err(Ok(Error::IndexOutOfBounds))
+    );
+    assert_eq!(
+        token.client().try_token_at(&-1),
+        Err(Ok(Error::IndexOutOfBounds))
+    );
+}
+
+#[test]
+fn test_mint_existing_id() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user1_id, &1);
+
+    let (auth, nonce) = token.auth(
+        &admin,
+        symbol!("mint"),
+        (&user2_id, &1i128, None::<Bytes>, None::<Royalty>),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user2_id, &1, &None, &None),
+        Err(Ok(Error::TokenExists))
+    );
+    assert_eq!(token.owner(&1), user1_id);
+}
+
+#[test]
+fn test_mint_next_skips_minted_ids() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
     let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
     let user_id = to_ed25519(&env, &user);
 
     token.initialize(&admin_id);
 
-    token.set_admin(&user, &user_id);
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &3);
+    open_free_sale(&env, &token, &admin);
+
+    let user1 = env.accounts().generate();
+    token.mint_next(&user1);
+    assert_eq!(token.owner(&2), (&user1).into());
+
+    let user2 = env.accounts().generate();
+    token.mint_next(&user2);
+    assert_eq!(token.owner(&4), (&user2).into());
+    assert_eq!(token.owner(&1), user_id);
+    assert_eq!(token.owner(&3), user_id);
+    assert_eq!(token.balance(&user_id), 2);
+}
+
+#[test]
+fn test_token_uri_not_minted() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+
+    token.initialize(&admin_id);
+
+    assert_eq!(
+        token.client().try_token_uri(&1),
+        Err(Ok(Error::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_burn_by_owner() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let operator_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &2);
+    token.appr(&user, &operator_id, &1);
+
+    token.burn(&user, &1);
+    assert_eq!(token.balance(&user_id), 1);
+    assert_eq!(token.nonce(&user_id), 2);
+    assert_eq!(token.supply(), 1);
+    assert_eq!(token.burned(), 1);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+    assert_eq!(token.token_of(&user_id, &0), 2);
+    assert_eq!(token.client().try_owner(&1), Err(Ok(Error::TokenNotFound)));
+    assert_eq!(
+        token.client().try_token_uri(&1),
+        Err(Ok(Error::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_burn_by_operator() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let operator1 = generate_keypair();
+    let operator1_id = to_ed25519(&env, &operator1);
+    let operator2 = generate_keypair();
+    let operator2_id = to_ed25519(&env, &operator2);
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &2);
+
+    token.appr(&user, &operator1_id, &1);
+    token.burn(&operator1, &1);
+
+    token.appr_all(&user, &operator2_id, &true);
+    token.burn(&operator2, &2);
+
+    assert_eq!(token.balance(&user_id), 0);
+    assert_eq!(token.supply(), 0);
+    assert_eq!(token.burned(), 2);
+}
+
+#[test]
+fn test_burn_non_authorized() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let attacker = generate_keypair();
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user_id, &1);
+    let (auth, nonce) = token.auth(&attacker, symbol!("burn"), (&1i128,));
+    assert_eq!(
+        token.client().try_burn(&auth, &nonce, &1),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_burn_not_minted() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+
+    token.initialize(&admin_id);
+
+    let (auth, nonce) = token.auth(&admin, symbol!("burn"), (&1i128,));
+    assert_eq!(
+        token.client().try_burn(&auth, &nonce, &1),
+        Err(Ok(Error::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_mint_burned_id() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user_id, &1);
+    token.burn(&admin, &1);
+
+    let (auth, nonce) = token.auth(
+        &admin,
+        symbol!("mint"),
+        (&user_id, &1i128, None::<Bytes>, None::<Royalty>),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::TokenExists))
+    );
+}
+
+#[test]
+fn test_xfer_clears_approval() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+    let operator = generate_keypair();
+    let operator_id = to_ed25519(&env, &operator);
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user1_id, &1);
+    token.appr(&user1, &operator_id, &1);
+    assert_eq!(token.get_appr(&1), operator_id);
+
+    token.xfer(&user1, &user2_id, &1);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+
+    let topics: Vec<RawVal> = (symbol!("appr"), zero_address(&env)).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (id, event_topics, data) = event.unwrap();
+        id == contract_id && event_topics == topics && i128::try_from_val(&env, data) == Ok(1)
+    }));
+}
+
+#[test]
+fn test_xfer_from_stale_approval() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+    let operator = generate_keypair();
+    let operator_id = to_ed25519(&env, &operator);
+
+    token.initialize(&admin_id);
+
+    token.mint(&admin, &user1_id, &1);
+    token.appr(&user1, &operator_id, &1);
+    token.xfer(&user1, &user2_id, &1);
+
+    // The operator approved by the previous owner can no longer move the token.
+    let (auth, nonce) = token.auth(
+        &operator,
+        symbol!("xfer_from"),
+        (&user2_id, &operator_id, &1i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user2_id, &operator_id, &nonce, &1),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+#[test]
+fn test_uri_pool() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    assert_eq!(token.uris().len(), 9);
+    assert!(token
+        .uris()
+        .contains(to_bytes(&env, NftURIs::Bacon.value())));
+
+    // Replace the default dogs with a single custom image.
+    for uri in token.uris().iter() {
+        token.remove_uri(&admin, &uri.unwrap());
+    }
+    assert!(token.uris().is_empty());
+
+    let custom = to_bytes(&env, "ipfs://custom.json");
+    token.add_uri(&admin, &custom);
+    assert_eq!(token.uris().len(), 1);
+
+    token.mint(&admin, &user_id, &1);
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    assert_eq!(token.token_uri(&1), custom);
+}
+
+#[test]
+fn test_uri_pool_empty() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    for uri in token.uris().iter() {
+        token.remove_uri(&admin, &uri.unwrap());
+    }
+
+    let (auth, nonce) = token.auth(
+        &admin,
+        symbol!("mint"),
+        (&user_id, &1i128, None::<Bytes>, None::<Royalty>),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::MetadataEmpty))
+    );
+}
+
+#[test]
+fn test_uri_pool_frozen() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+
+    // A token minted with its own uri does not draw from the pool.
+    let custom = to_bytes(&env, "ipfs://custom.json");
+    token.mint_with_uri(&admin, &user_id, &1, &custom);
+    let late = to_bytes(&env, "images/late.png");
+    token.add_uri(&admin, &late);
+
+    // The first pool mint freezes the pool before the reveal.
+    token.mint(&admin, &user_id, &2);
+    let extra = to_bytes(&env, "images/extra.png");
+    let (auth, nonce) = token.auth(&admin, symbol!("add_uri"), (&extra,));
+    assert_eq!(
+        token.client().try_add_uri(&auth, &nonce, &extra),
+        Err(Ok(Error::PoolFrozen))
+    );
+
+    let (auth, nonce) = token.auth(&admin, symbol!("remove_uri"), (&late,));
+    assert_eq!(
+        token.client().try_remove_uri(&auth, &nonce, &late),
+        Err(Ok(Error::PoolFrozen))
+    );
+    assert_eq!(token.uris().len(), 10);
+}
+
+#[test]
+fn test_reveal() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+    assert!(!token.client().revealed());
+    assert_eq!(token.token_uri(&1), to_bytes(&env, "images/logo.png"));
+
+    let wrong = to_bytes(&env, "guessed seed");
+    let (auth, nonce) = token.auth(&admin, symbol!("reveal"), (&wrong,));
+    assert_eq!(
+        token.client().try_reveal(&auth, &nonce, &wrong),
+        Err(Ok(Error::InvalidSeed))
+    );
+
+    let seed = to_bytes(&env, TOKEN_SEED);
+    token.reveal(&admin, &seed);
+    assert!(token.client().revealed());
+    assert!(token.uris().contains(token.token_uri(&1)));
+
+    let topics: Vec<RawVal> = (symbol!("reveal"), admin_id.clone()).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (_, event_topics, _) = event.unwrap();
+        event_topics == topics
+    }));
+
+    // The pool and the assignments it produced are frozen after the reveal.
+    let (auth, nonce) = token.auth(&admin, symbol!("reveal"), (&seed,));
+    assert_eq!(
+        token.client().try_reveal(&auth, &nonce, &seed),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+
+    let uri = to_bytes(&env, "images/late.png");
+    let (auth, nonce) = token.auth(&admin, symbol!("add_uri"), (&uri,));
+    assert_eq!(
+        token.client().try_add_uri(&auth, &nonce, &uri),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+
+    let bacon = to_bytes(&env, NftURIs::Bacon.value());
+    let (auth, nonce) = token.auth(&admin, symbol!("remove_uri"), (&bacon,));
+    assert_eq!(
+        token.client().try_remove_uri(&auth, &nonce, &bacon),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+
+    let (auth, nonce) = token.auth(
+        &admin,
+        symbol!("mint"),
+        (&user_id, &2i128, None::<Bytes>, None::<Royalty>),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &2, &None, &None),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+}
+
+#[test]
+fn test_hidden_uri() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    let seed = to_bytes(&env, TOKEN_SEED);
+    assert_eq!(token.client().provenance(), env.crypto().sha256(&seed));
+
+    assert_eq!(
+        token.client().hidden_uri(),
+        to_bytes(&env, "images/logo.png")
+    );
+
+    let hidden = to_bytes(&env, "ipfs://QmHidden/unrevealed.json");
+    token.set_hidden(&admin, &hidden);
+    assert_eq!(token.client().hidden_uri(), hidden);
+
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &2);
+    assert_eq!(token.token_uri(&1), hidden);
+    assert_eq!(token.token_uri(&2), hidden);
+
+    let (auth, nonce) = token.auth(&user, symbol!("set_hidden"), (&hidden,));
+    assert_eq!(
+        token.client().try_set_hidden(&auth, &nonce, &hidden),
+        Err(Ok(Error::MissingRole))
+    );
+
+    // Clearing the placeholder leaves pool tokens without a uri until the reveal.
+    token.set_hidden(&admin, &Bytes::new(&env));
+    assert_eq!(
+        token.client().try_token_uri(&1),
+        Err(Ok(Error::NotRevealed))
+    );
+
+    token.reveal(&admin, &seed);
+    assert!(token.uris().contains(token.token_uri(&1)));
+    assert!(token.uris().contains(token.token_uri(&2)));
+}
+
+#[test]
+fn test_rand_index_distribution() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    // A fixed minter keeps the sampled indexes, and so the bounds below, deterministic.
+    let minter = Identifier::Ed25519(BytesN::from_array(&env, &[7; 32]));
+
+    let mut counts = [0u32; 9];
+    env.as_contract(&contract_id, || {
+        let seed = to_bytes(&env, TOKEN_SEED);
+        write_seed_hash(&env, env.crypto().sha256(&seed));
+        write_seed(&env, seed).unwrap();
+        for id in 0..900 {
+            counts[rand_index(&env, minter.clone(), id, 9) as usize] += 1;
+        }
+    });
+
+    // Each of the 9 images is expected 100 times; allow for ordinary sampling noise.
+    for count in counts {
+        assert!(
+            (60..=140).contains(&count),
+            "skewed distribution: {:?}",
+            counts
+        );
+    }
+}
+
+#[test]
+fn test_mint_with_uri() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+
+    let uri = to_bytes(&env, "ar://one-of-one.json");
+    token.mint_with_uri(&admin, &user_id, &1, &uri);
+    assert_eq!(token.token_uri(&1), to_bytes(&env, "images/logo.png"));
+
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    assert_eq!(token.token_uri(&1), uri);
+    assert!(!token.uris().contains(uri));
+}
+
+#[test]
+fn test_uri_pool_errors() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+
+    token.initialize(&admin_id);
+
+    let bacon = to_bytes(&env, NftURIs::Bacon.value());
+    let (auth, nonce) = token.auth(&admin, symbol!("add_uri"), (&bacon,));
+    assert_eq!(
+        token.client().try_add_uri(&auth, &nonce, &bacon),
+        Err(Ok(Error::MetadataExists))
+    );
+
+    let missing = to_bytes(&env, "images/missing.png");
+    let (auth, nonce) = token.auth(&admin, symbol!("remove_uri"), (&missing,));
+    assert_eq!(
+        token.client().try_remove_uri(&auth, &nonce, &missing),
+        Err(Ok(Error::MetadataNotFound))
+    );
+
+    let (auth, nonce) = token.auth(&user, symbol!("add_uri"), (&missing,));
+    assert_eq!(
+        token.client().try_add_uri(&auth, &nonce, &missing),
+        Err(Ok(Error::MissingRole))
+    );
+}
+
+#[test]
+fn test_base_uri() {
+    let env = Env::default();
+    let contract_id = register_contract(&env);
+    let token = Token::new(&env, &contract_id);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+
+    // Tokens minted before the base uri is set keep their uri from the pool.
+    token.mint(&admin, &user_id, &1);
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    let pool_uri = token.token_uri(&1);
+    assert!(token.uris().contains(&pool_uri));
+
+    let base = to_bytes(&env, "ipfs://QmBase/");
+    let suffix = to_bytes(&env, ".json");
+    token.set_base(&admin, &base, &suffix);
+    assert_eq!(token.base_uri(), base);
+    assert_eq!(token.client().uri_suffix(), suffix);
+
+    let topics: Vec<RawVal> = (symbol!("meta_upd"), admin_id.clone()).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (id, event_topics, _) = event.unwrap();
+        id == contract_id && event_topics == topics
+    }));
+
+    token.mint(&admin, &user_id, &42);
+    token.mint(&admin, &user_id, &1000);
+    assert_eq!(token.token_uri(&1), pool_uri);
+    assert_eq!(
+        token.token_uri(&42),
+        to_bytes(&env, "ipfs://QmBase/42.json")
+    );
+    assert_eq!(
+        token.token_uri(&1000),
+        to_bytes(&env, "ipfs://QmBase/1000.json")
+    );
+
+    token.set_base(&admin, &to_bytes(&env, "ar://tx/"), &to_bytes(&env, ""));
+    assert_eq!(token.token_uri(&42), to_bytes(&env, "ar://tx/42"));
+}
+
+#[test]
+fn test_base_uri_hidden() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.set_base(
+        &admin,
+        &to_bytes(&env, "ipfs://QmBase/"),
+        &to_bytes(&env, ".json"),
+    );
+
+    // Tokens with final metadata under the base uri still show the placeholder until the reveal.
+    token.mint(&admin, &user_id, &42);
+    assert_eq!(token.token_uri(&42), to_bytes(&env, "images/logo.png"));
+
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    assert_eq!(
+        token.token_uri(&42),
+        to_bytes(&env, "ipfs://QmBase/42.json")
+    );
+}
+
+#[test]
+fn test_set_uri_override() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    token.set_base(
+        &admin,
+        &to_bytes(&env, "https://example.com/"),
+        &to_bytes(&env, ".json"),
+    );
+
+    token.mint(&admin, &user_id, &7);
+    assert_eq!(
+        token.token_uri(&7),
+        to_bytes(&env, "https://example.com/7.json")
+    );
+
+    let special = to_bytes(&env, "ipfs://QmSpecial");
+    token.set_uri(&admin, &7, &Some(special.clone()));
+    assert_eq!(token.token_uri(&7), special);
+
+    token.set_uri(&admin, &7, &None);
+    assert_eq!(
+        token.token_uri(&7),
+        to_bytes(&env, "https://example.com/7.json")
+    );
+
+    let (auth, nonce) = token.auth(&admin, symbol!("set_uri"), (&8i128, &Some(special.clone())));
+    assert_eq!(
+        token
+            .client()
+            .try_set_uri(&auth, &nonce, &8, &Some(special)),
+        Err(Ok(Error::TokenNotFound))
+    );
+}
+
+#[test]
+fn test_royalty() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let artist_id = to_ed25519(&env, &generate_keypair());
+    let collector_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+    assert_eq!(token.royalty(&1, &1000), (zero_address(&env), 0));
+
+    let default = Royalty {
+        receiver: admin_id.clone(),
+        bps: 500,
+    };
+    token.set_royal(&admin, &default);
+    assert_eq!(token.client().get_royal(), Some(default));
+    assert_eq!(token.royalty(&1, &1000), (admin_id.clone(), 50));
+
+    let topics: Vec<RawVal> = (symbol!("set_royal"), admin_id.clone()).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (_, event_topics, _) = event.unwrap();
+        event_topics == topics
+    }));
+
+    let royalty = Royalty {
+        receiver: artist_id.clone(),
+        bps: 1000,
+    };
+    token.mint_with_royalty(&admin, &user_id, &2, &royalty);
+    assert_eq!(token.royalty(&2, &1000), (artist_id, 100));
+    assert_eq!(token.royalty(&1, &1000), (admin_id, 50));
+
+    // The override travels with the token, not with its owner.
+    token.xfer(&user, &collector_id, &2);
+    assert_eq!(token.royalty(&2, &99), (royalty.receiver, 9));
+}
+
+#[test]
+fn test_royalty_errors() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    assert_eq!(
+        token.client().try_royalty(&1, &-1),
+        Err(Ok(Error::NegativeAmount))
+    );
+    assert_eq!(
+        token.client().try_royalty(&2, &1000),
+        Err(Ok(Error::TokenNotFound))
+    );
+
+    let royalty = Royalty {
+        receiver: admin_id,
+        bps: MAX_BPS + 1,
+    };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_royal"), (&royalty,));
+    assert_eq!(
+        token.client().try_set_royal(&auth, &nonce, &royalty),
+        Err(Ok(Error::InvalidRoyalty))
+    );
+
+    let some = Some(royalty);
+    let (auth, nonce) = token.auth(
+        &admin,
+        symbol!("mint"),
+        (&user_id, &2i128, None::<Bytes>, &some),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &2, &None, &some),
+        Err(Ok(Error::InvalidRoyalty))
+    );
+
+    let royalty = Royalty {
+        receiver: user_id.clone(),
+        bps: 500,
+    };
+    let (auth, nonce) = token.auth(&user, symbol!("set_royal"), (&royalty,));
+    assert_eq!(
+        token.client().try_set_royal(&auth, &nonce, &royalty),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn test_royalty_amount() {
+    assert_eq!(royalty_amount(0, 500), 0);
+    assert_eq!(royalty_amount(19, 500), 0);
+    assert_eq!(royalty_amount(20, 500), 1);
+    assert_eq!(royalty_amount(10_001, 2_500), 2_500);
+    assert_eq!(royalty_amount(123_456_789, 250), 3_086_419);
+
+    // Prices close to the i128 limit must not overflow.
+    assert_eq!(royalty_amount(i128::MAX, MAX_BPS), i128::MAX);
+    assert_eq!(royalty_amount(i128::MAX, 5_000), i128::MAX / 2);
+    assert_eq!(royalty_amount(i128::MAX, 1), i128::MAX / 10_000);
+}
+
+#[test]
+fn test_xfer_pay() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+    let artist_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.set_royal(
+        &admin,
+        &Royalty {
+            receiver: artist_id.clone(),
+            bps: 750,
+        },
+    );
+    token.mint(&admin, &seller_id, &1);
+    token.appr(&seller, &admin_id, &1);
+
+    payment.mint(&buyer_id, &1000);
+    payment.approve(&buyer, &token.contract_id(), &1000);
+
+    token.xfer_pay(&seller, &buyer, &1, &payment.price(1000));
+    assert_eq!(token.owner(&1), buyer_id);
+    assert_eq!(token.balance(&seller_id), 0);
+    assert_eq!(token.balance(&buyer_id), 1);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+
+    assert_eq!(payment.balance(&artist_id), 75);
+    assert_eq!(payment.balance(&seller_id), 925);
+    assert_eq!(payment.balance(&buyer_id), 0);
+}
+
+#[test]
+fn test_xfer_pay_without_royalty() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    payment.mint(&buyer_id, &500);
+    payment.approve(&buyer, &token.contract_id(), &500);
+
+    token.xfer_pay(&seller, &buyer, &1, &payment.price(500));
+    assert_eq!(token.owner(&1), buyer_id);
+    assert_eq!(payment.balance(&seller_id), 500);
+}
+
+#[test]
+fn test_xfer_pay_errors() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    let price = payment.price(100);
+    let (from_auth, from_nonce) =
+        token.auth(&buyer, symbol!("xfer_pay"), (&seller_id, &1i128, &price));
+    let (to_auth, to_nonce) = token.auth(&seller, symbol!("xfer_pay"), (&buyer_id, &1i128, &price));
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_pay(&from_auth, &from_nonce, &to_auth, &to_nonce, &1, &price),
+        Err(Ok(Error::NotOwner))
+    );
+
+    let price = payment.price(-1);
+    let (from_auth, from_nonce) =
+        token.auth(&seller, symbol!("xfer_pay"), (&buyer_id, &1i128, &price));
+    let (to_auth, to_nonce) = token.auth(&buyer, symbol!("xfer_pay"), (&seller_id, &1i128, &price));
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_pay(&from_auth, &from_nonce, &to_auth, &to_nonce, &1, &price),
+        Err(Ok(Error::NegativeAmount))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_xfer_pay_without_allowance() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+    payment.mint(&buyer_id, &1000);
+
+    token.xfer_pay(&seller, &buyer, &1, &payment.price(1000));
+}
+
+#[test]
+#[should_panic(expected = "Failed ED25519 verification")]
+fn test_xfer_pay_price_tampered() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+    payment.mint(&buyer_id, &1000);
+    payment.approve(&buyer, &token.contract_id(), &1000);
+
+    // The buyer agreed to pay 10, not the 1000 claimed by the seller.
+    let price = payment.price(1000);
+    let (from_auth, from_nonce) =
+        token.auth(&seller, symbol!("xfer_pay"), (&buyer_id, &1i128, &price));
+    let (to_auth, to_nonce) = token.auth(
+        &buyer,
+        symbol!("xfer_pay"),
+        (&seller_id, &1i128, &payment.price(10)),
+    );
+    token
+        .client()
+        .xfer_pay(&from_auth, &from_nonce, &to_auth, &to_nonce, &1, &price);
+}
+
+#[test]
+fn test_marketplace() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+    let buyer_id = to_ed25519(&env, &buyer);
+
+    token.initialize(&admin_id);
+    token.set_royal(
+        &admin,
+        &Royalty {
+            receiver: admin_id.clone(),
+            bps: 1000,
+        },
+    );
+    token.mint(&admin, &seller_id, &1);
+    token.appr(&seller, &admin_id, &1);
+
+    let price = payment.price(200);
+    token.list(&seller, &1, &price);
+    assert_eq!(token.listing(&1), Some(price.clone()));
+    assert_eq!(token.owner(&1), seller_id);
+
+    payment.mint(&buyer_id, &200);
+    payment.approve(&buyer, &token.contract_id(), &200);
+    token.buy(&buyer, &1, &price);
+
+    assert_eq!(token.owner(&1), buyer_id);
+    assert_eq!(token.balance(&seller_id), 0);
+    assert_eq!(token.balance(&buyer_id), 1);
+    assert_eq!(token.token_of(&buyer_id, &0), 1);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+    assert_eq!(token.listing(&1), None);
+    assert_eq!(payment.balance(&admin_id), 20);
+    assert_eq!(payment.balance(&seller_id), 180);
+
+    let topics: Vec<RawVal> = (symbol!("sale"), seller_id, buyer_id).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (_, event_topics, _) = event.unwrap();
+        event_topics == topics
+    }));
+}
+
+#[test]
+fn test_delist() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    let price = payment.price(200);
+    token.list(&seller, &1, &price);
+    token.delist(&seller, &1);
+    assert_eq!(token.listing(&1), None);
+
+    let topics: Vec<RawVal> = (symbol!("delist"), seller_id).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (_, event_topics, _) = event.unwrap();
+        event_topics == topics
+    }));
+
+    let (auth, nonce) = token.auth(&buyer, symbol!("buy"), (&1i128, &price));
+    assert_eq!(
+        token.client().try_buy(&auth, &nonce, &1, &price),
+        Err(Ok(Error::NotListed))
+    );
+
+    let (auth, nonce) = token.auth(&seller, symbol!("delist"), (&1i128,));
+    assert_eq!(
+        token.client().try_delist(&auth, &nonce, &1),
+        Err(Ok(Error::NotListed))
+    );
+}
+
+#[test]
+fn test_listing_errors() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let buyer = generate_keypair();
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    let price = payment.price(200);
+    let (auth, nonce) = token.auth(&buyer, symbol!("list"), (&1i128, &price));
+    assert_eq!(
+        token.client().try_list(&auth, &nonce, &1, &price),
+        Err(Ok(Error::NotOwner))
+    );
+
+    let negative = payment.price(-200);
+    let (auth, nonce) = token.auth(&seller, symbol!("list"), (&1i128, &negative));
+    assert_eq!(
+        token.client().try_list(&auth, &nonce, &1, &negative),
+        Err(Ok(Error::NegativeAmount))
+    );
+
+    // The seller raising the price must not charge a buyer who agreed to the old one.
+    token.list(&seller, &1, &price);
+    token.list(&seller, &1, &payment.price(2000));
+    let (auth, nonce) = token.auth(&buyer, symbol!("buy"), (&1i128, &price));
+    assert_eq!(
+        token.client().try_buy(&auth, &nonce, &1, &price),
+        Err(Ok(Error::PriceMismatch))
+    );
+}
+
+#[test]
+fn test_mint_clears_stale_state() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let thief_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+
+    // Ids that were never minted have no owner who could approve, list or auction them.
+    let price = payment.price(0);
+    let (auth, nonce) = token.auth(&user, symbol!("appr"), (&thief_id, &5i128, ()));
+    assert_eq!(
+        token.client().try_appr(&auth, &nonce, &thief_id, &5, &None),
+        Err(Ok(Error::TokenNotFound))
+    );
+    let (auth, nonce) = token.auth(&user, symbol!("list"), (&5i128, &price));
+    assert_eq!(
+        token.client().try_list(&auth, &nonce, &5, &price),
+        Err(Ok(Error::TokenNotFound))
+    );
+    let (auth, nonce) = token.auth(
+        &user,
+        symbol!("start_auc"),
+        (&5i128, &price, &100u64, &1i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_start_auc(&auth, &nonce, &5, &price, &100, &1),
+        Err(Ok(Error::TokenNotFound))
+    );
+
+    // State left behind for an id anyway does not carry over to its first owner.
+    env.as_contract(&token.contract_id(), || {
+        write_approval(&env, 5, thief_id.clone(), None);
+        write_listing(&env, 5, price.clone());
+    });
+    token.mint(&admin, &user_id, &5);
+    assert_eq!(token.client().get_appr(&5), zero_address(&env));
+    assert_eq!(token.listing(&5), None);
+}
+
+#[test]
+fn test_xfer_cancels_listing() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+    token.mint(&admin, &seller_id, &2);
+
+    token.list(&seller, &1, &payment.price(200));
+    token.xfer(&seller, &user_id, &1);
+    assert_eq!(token.listing(&1), None);
+
+    // Transferring the token back does not revive the old listing.
+    token.xfer(&user, &seller_id, &1);
+    assert_eq!(token.listing(&1), None);
+
+    token.list(&seller, &2, &payment.price(200));
+    token.burn(&seller, &2);
+    assert_eq!(token.listing(&2), None);
+}
+
+fn set_timestamp(env: &Env, timestamp: u64) {
+    env.ledger().with_mut(|ledger| ledger.timestamp = timestamp);
+}
+
+#[test]
+fn test_auction() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let bidder1 = generate_keypair();
+    let bidder1_id = to_ed25519(&env, &bidder1);
+    let bidder2 = generate_keypair();
+    let bidder2_id = to_ed25519(&env, &bidder2);
+
+    token.initialize(&admin_id);
+    token.set_royal(
+        &admin,
+        &Royalty {
+            receiver: admin_id.clone(),
+            bps: 500,
+        },
+    );
+    token.mint(&admin, &seller_id, &1);
+
+    for (bidder, bidder_id) in [(&bidder1, &bidder1_id), (&bidder2, &bidder2_id)] {
+        payment.mint(bidder_id, &1000);
+        payment.approve(bidder, &token.contract_id(), &1000);
+    }
+
+    set_timestamp(&env, 100);
+    token.start_auc(&seller, &1, &payment.price(100), &200, &10);
+    let escrow = Identifier::Contract(token.contract_id());
+    assert_eq!(token.owner(&1), escrow);
+    assert_eq!(token.balance(&seller_id), 0);
+    assert_eq!(token.balance(&escrow), 1);
+
+    token.bid(&bidder1, &1, &100);
+    assert_eq!(payment.balance(&bidder1_id), 900);
+    assert_eq!(payment.balance(&escrow), 100);
+
+    // Outbidding refunds the previous highest bidder.
+    set_timestamp(&env, 150);
+    token.bid(&bidder2, &1, &110);
+    assert_eq!(payment.balance(&bidder1_id), 1000);
+    assert_eq!(payment.balance(&bidder2_id), 890);
+    assert_eq!(payment.balance(&escrow), 110);
+
+    let auction = token.auction(&1).unwrap();
+    assert_eq!(auction.bidder, bidder2_id);
+    assert_eq!(auction.bid, 110);
+
+    set_timestamp(&env, 200);
+    token.settle(&1);
+    assert_eq!(token.owner(&1), bidder2_id);
+    assert_eq!(token.balance(&bidder2_id), 1);
+    assert_eq!(token.balance(&escrow), 0);
+    assert_eq!(token.auction(&1), None);
+    assert_eq!(payment.balance(&escrow), 0);
+    assert_eq!(payment.balance(&admin_id), 5);
+    assert_eq!(payment.balance(&seller_id), 105);
+
+    let topics: Vec<RawVal> = (symbol!("settle"), seller_id, bidder2_id).into_val(&env);
+    assert!(env.events().all().iter().any(|event| {
+        let (_, event_topics, _) = event.unwrap();
+        event_topics == topics
+    }));
+}
+
+#[test]
+fn test_auction_without_bids() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    token.start_auc(&seller, &1, &payment.price(100), &50, &10);
+    assert_eq!(
+        token.client().try_settle(&1),
+        Err(Ok(Error::AuctionNotEnded))
+    );
+
+    set_timestamp(&env, 50);
+    token.settle(&1);
+    assert_eq!(token.owner(&1), seller_id);
+    assert_eq!(token.balance(&seller_id), 1);
+    assert_eq!(token.client().try_settle(&1), Err(Ok(Error::NotAuctioned)));
+}
+
+#[test]
+fn test_auction_bid_errors() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let bidder = generate_keypair();
+    let bidder_id = to_ed25519(&env, &bidder);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+    token.mint(&admin, &seller_id, &2);
+    payment.mint(&bidder_id, &1000);
+    payment.approve(&bidder, &token.contract_id(), &1000);
+
+    let (auth, nonce) = token.auth(&bidder, symbol!("bid"), (&2i128, &100i128));
+    assert_eq!(
+        token.client().try_bid(&auth, &nonce, &2, &100),
+        Err(Ok(Error::NotAuctioned))
+    );
+
+    token.start_auc(&seller, &1, &payment.price(100), &100, &10);
+
+    let (auth, nonce) = token.auth(&bidder, symbol!("bid"), (&1i128, &99i128));
+    assert_eq!(
+        token.client().try_bid(&auth, &nonce, &1, &99),
+        Err(Ok(Error::BidTooLow))
+    );
+
+    token.bid(&bidder, &1, &100);
+    let (auth, nonce) = token.auth(&bidder, symbol!("bid"), (&1i128, &109i128));
+    assert_eq!(
+        token.client().try_bid(&auth, &nonce, &1, &109),
+        Err(Ok(Error::BidTooLow))
+    );
+
+    set_timestamp(&env, 100);
+    let (auth, nonce) = token.auth(&bidder, symbol!("bid"), (&1i128, &200i128));
+    assert_eq!(
+        token.client().try_bid(&auth, &nonce, &1, &200),
+        Err(Ok(Error::AuctionEnded))
+    );
+}
+
+#[test]
+fn test_auction_escrow() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let seller = generate_keypair();
+    let seller_id = to_ed25519(&env, &seller);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &seller_id, &1);
+
+    set_timestamp(&env, 100);
+    let reserve = payment.price(100);
+    let (auth, nonce) = token.auth(
+        &seller,
+        symbol!("start_auc"),
+        (&1i128, &reserve, &100u64, &10i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_start_auc(&auth, &nonce, &1, &reserve, &100, &10),
+        Err(Ok(Error::InvalidAuction))
+    );
+    let (auth, nonce) = token.auth(
+        &seller,
+        symbol!("start_auc"),
+        (&1i128, &reserve, &200u64, &0i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_start_auc(&auth, &nonce, &1, &reserve, &200, &0),
+        Err(Ok(Error::InvalidAuction))
+    );
+
+    token.list(&seller, &1, &payment.price(500));
+    token.start_auc(&seller, &1, &reserve, &200, &10);
+    assert_eq!(token.listing(&1), None);
+
+    // The seller gives up control of the token while it is in escrow.
+    let (auth, nonce) = token.auth(&seller, symbol!("xfer"), (&user_id, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &user_id, &1),
+        Err(Ok(Error::TokenEscrowed))
+    );
+    let (auth, nonce) = token.auth(&admin, symbol!("burn"), (&1i128,));
+    assert_eq!(
+        token.client().try_burn(&auth, &nonce, &1),
+        Err(Ok(Error::TokenEscrowed))
+    );
+    assert_eq!(token.royalty(&1, &100), (zero_address(&env), 0));
+}
+
+#[test]
+fn test_paid_mint() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+    let treasury_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &admin_id, &1);
+
+    let sale = Sale {
+        price: payment.price(50),
+        limit: 2,
+        max_supply: 4,
+        start: 100,
+        end: 200,
+    };
+    token.set_sale(&admin, &sale);
+    assert_eq!(token.client().sale(), Some(sale.clone()));
+
+    for (user, user_id) in [(&user1, &user1_id), (&user2, &user2_id)] {
+        payment.mint(user_id, &1000);
+        payment.approve(user, &token.contract_id(), &1000);
+    }
+
+    let (auth, nonce) = token.auth(&user1, symbol!("mint_paid"), ());
+    assert_eq!(
+        token.client().try_mint_paid(&auth, &nonce),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    set_timestamp(&env, 100);
+    token.mint_paid(&user1);
+    token.mint_paid(&user1);
+    assert_eq!(token.owner(&2), user1_id);
+    assert_eq!(token.owner(&3), user1_id);
+    assert_eq!(token.client().minted(&user1_id), 2);
+    assert_eq!(payment.balance(&user1_id), 900);
+
+    let (auth, nonce) = token.auth(&user1, symbol!("mint_paid"), ());
+    assert_eq!(
+        token.client().try_mint_paid(&auth, &nonce),
+        Err(Ok(Error::AlreadyMinted))
+    );
+
+    // The administrator's token counts towards the maximum supply, and so do burned tokens.
+    token.mint_paid(&user2);
+    token.burn(&user2, &4);
+    let (auth, nonce) = token.auth(&user2, symbol!("mint_paid"), ());
+    assert_eq!(
+        token.client().try_mint_paid(&auth, &nonce),
+        Err(Ok(Error::SoldOut))
+    );
+
+    set_timestamp(&env, 200);
+    let (auth, nonce) = token.auth(&user2, symbol!("mint_paid"), ());
+    assert_eq!(
+        token.client().try_mint_paid(&auth, &nonce),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    let payment_id = sale.price.token;
+    assert_eq!(token.client().proceeds(&payment_id), 150);
+    token.withdraw(&admin, &payment_id, &treasury_id);
+    assert_eq!(payment.balance(&treasury_id), 150);
+    assert_eq!(token.client().proceeds(&payment_id), 0);
+
+    let (auth, nonce) = token.auth(&user1, symbol!("withdraw"), (&payment_id, &user1_id));
+    assert_eq!(
+        token
+            .client()
+            .try_withdraw(&auth, &nonce, &payment_id, &user1_id),
+        Err(Ok(Error::NotAdmin))
+    );
+}
+
+#[test]
+fn test_withdraw_leaves_bids_in_escrow() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.set_sale(
+        &admin,
+        &Sale {
+            price: payment.price(50),
+            limit: 1,
+            max_supply: 10,
+            start: 0,
+            end: 100,
+        },
+    );
+    token.mint(&admin, &admin_id, &7);
+
+    payment.mint(&user_id, &1000);
+    payment.approve(&user, &token.contract_id(), &1000);
+    token.mint_paid(&user);
+
+    token.start_auc(&admin, &7, &payment.price(100), &100, &10);
+    token.bid(&user, &7, &300);
+
+    token.withdraw(&admin, &payment.price(0).token, &admin_id);
+    assert_eq!(payment.balance(&admin_id), 50);
+    assert_eq!(
+        payment.balance(&Identifier::Contract(token.contract_id())),
+        300
+    );
+}
+
+#[test]
+fn test_sale_errors() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+
+    token.initialize(&admin_id);
+
+    let sale = Sale {
+        price: payment.price(50),
+        limit: 1,
+        max_supply: 10,
+        start: 100,
+        end: 100,
+    };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_sale"), (&sale,));
+    assert_eq!(
+        token.client().try_set_sale(&auth, &nonce, &sale),
+        Err(Ok(Error::InvalidSale))
+    );
+
+    let sale = Sale {
+        limit: 0,
+        end: 200,
+        ..sale
+    };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_sale"), (&sale,));
+    assert_eq!(
+        token.client().try_set_sale(&auth, &nonce, &sale),
+        Err(Ok(Error::InvalidSale))
+    );
+}
+
+#[test]
+fn test_presale() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2 = generate_keypair();
+    let user2_id = to_ed25519(&env, &user2);
+    let user3 = generate_keypair();
+    let user3_id = to_ed25519(&env, &user3);
+    let outsider = generate_keypair();
+    let outsider_id = to_ed25519(&env, &outsider);
+
+    // Allowlist of user1 for 2 tokens, user2 for 1 token and user3 for 1 token.
+    let leaf1 = leaf_hash(&env, &user1_id, 2);
+    let leaf2 = leaf_hash(&env, &user2_id, 1);
+    let leaf3 = leaf_hash(&env, &user3_id, 1);
+    let node12 = node_hash(&env, &leaf1, &leaf2);
+    let root = node_hash(&env, &node12, &leaf3);
+    let proof1: Vec<BytesN<32>> = vec![&env, leaf2.clone(), leaf3.clone()];
+    let proof2: Vec<BytesN<32>> = vec![&env, leaf1, leaf3];
+    let proof3: Vec<BytesN<32>> = vec![&env, node12];
+
+    token.initialize(&admin_id);
+    let presale = Presale {
+        root,
+        price: payment.price(30),
+        start: 100,
+        end: 200,
+    };
+    token.set_pre(&admin, &presale);
+    token.set_sale(
+        &admin,
+        &Sale {
+            price: payment.price(50),
+            limit: 1,
+            max_supply: 100,
+            start: 200,
+            end: 300,
+        },
+    );
+
+    for (user, user_id) in [
+        (&user1, &user1_id),
+        (&user2, &user2_id),
+        (&outsider, &outsider_id),
+    ] {
+        payment.mint(user_id, &1000);
+        payment.approve(user, &token.contract_id(), &1000);
+    }
+
+    let (auth, nonce) = token.auth(&user1, symbol!("mint_pre"), (&2i128, &proof1));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &2, &proof1),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    // Presale phase: only allowlisted identifiers, up to their allowance.
+    set_timestamp(&env, 100);
+    token.mint_pre(&user1, &2, &proof1);
+    token.mint_pre(&user1, &2, &proof1);
+    token.mint_pre(&user2, &1, &proof2);
+    assert_eq!(token.balance(&user1_id), 2);
+    assert_eq!(token.balance(&user2_id), 1);
+    assert_eq!(token.client().pre_minted(&user1_id), 2);
+    assert_eq!(payment.balance(&user1_id), 940);
+
+    let (auth, nonce) = token.auth(&user1, symbol!("mint_pre"), (&2i128, &proof1));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &2, &proof1),
+        Err(Ok(Error::AlreadyMinted))
+    );
+
+    // Claiming a larger allowance than the one on the allowlist fails the proof.
+    let (auth, nonce) = token.auth(&user2, symbol!("mint_pre"), (&5i128, &proof2));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &5, &proof2),
+        Err(Ok(Error::InvalidProof))
+    );
+
+    // Proofs are bound to the identifier they were made for.
+    let (auth, nonce) = token.auth(&outsider, symbol!("mint_pre"), (&1i128, &proof3));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &1, &proof3),
+        Err(Ok(Error::InvalidProof))
+    );
+
+    let (auth, nonce) = token.auth(&outsider, symbol!("mint_paid"), ());
+    assert_eq!(
+        token.client().try_mint_paid(&auth, &nonce),
+        Err(Ok(Error::SaleNotActive))
+    );
+
+    // Public phase: the presale closes and anyone can mint.
+    set_timestamp(&env, 200);
+    let (auth, nonce) = token.auth(&user2, symbol!("mint_pre"), (&1i128, &proof2));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &1, &proof2),
+        Err(Ok(Error::SaleNotActive))
+    );
+    token.mint_paid(&outsider);
+    assert_eq!(token.balance(&outsider_id), 1);
+
+    assert_eq!(token.client().proceeds(&presale.price.token), 140);
+}
+
+#[test]
+fn test_presale_limits() {
+    let (env, token) = Token::create();
+    let payment = PaymentToken::create(&env);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    // A single leaf is its own root and needs no proof.
+    let root = leaf_hash(&env, &user_id, 3);
+    let proof: Vec<BytesN<32>> = Vec::new(&env);
+
+    token.initialize(&admin_id);
+    let sale = Sale {
+        price: payment.price(0),
+        limit: 1,
+        max_supply: 2,
+        start: 200,
+        end: 300,
+    };
+    token.set_sale(&admin, &sale);
+
+    // Both windows would be open at time 200.
+    let presale = Presale {
+        root,
+        price: payment.price(0),
+        start: 100,
+        end: 201,
+    };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_pre"), (&presale,));
+    assert_eq!(
+        token.client().try_set_pre(&auth, &nonce, &presale),
+        Err(Ok(Error::SaleOverlap))
+    );
+
+    let presale = Presale {
+        end: 200,
+        ..presale
+    };
+    token.set_pre(&admin, &presale);
+
+    let moved = Sale { start: 150, ..sale };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_sale"), (&moved,));
+    assert_eq!(
+        token.client().try_set_sale(&auth, &nonce, &moved),
+        Err(Ok(Error::SaleOverlap))
+    );
+
+    // The allowance leaves room for a third token, but the maximum supply of the sale does not.
+    set_timestamp(&env, 100);
+    token.mint_pre(&user, &3, &proof);
+    token.mint_pre(&user, &3, &proof);
+    let (auth, nonce) = token.auth(&user, symbol!("mint_pre"), (&3i128, &proof));
+    assert_eq!(
+        token.client().try_mint_pre(&auth, &nonce, &3, &proof),
+        Err(Ok(Error::SoldOut))
+    );
+    assert_eq!(token.supply(), 2);
+}
+
+#[test]
+fn test_mint_batch() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1_id = to_ed25519(&env, &generate_keypair());
+    let user2_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+
+    let to = vec![&env, user1_id.clone(), user2_id.clone(), user1_id.clone()];
+    let ids = vec![&env, 10i128, 20, 30];
+    token.mint_batch(&admin, &to, &ids);
+
+    assert_eq!(token.nonce(&admin_id), 1);
+    assert_eq!(token.supply(), 3);
+    assert_eq!(token.balance(&user1_id), 2);
+    assert_eq!(token.balance(&user2_id), 1);
+    assert_eq!(token.owner(&20), user2_id);
+    assert_eq!(token.token_of(&user1_id, &1), 30);
+
+    let topics: Vec<RawVal> = (symbol!("mint"), user1_id.clone()).into_val(&env);
+    let mints = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| {
+            let (_, event_topics, _) = event.as_ref().unwrap();
+            event_topics == &topics
+        })
+        .count();
+    assert_eq!(mints, 2);
+}
+
+#[test]
+fn test_mint_batch_rollback() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &3);
+
+    // The last item fails, so none of the batch is minted.
+    let to = vec![&env, user_id.clone(), user_id.clone(), user_id.clone()];
+    let ids = vec![&env, 1i128, 2, 3];
+    let (auth, nonce) = token.auth(&admin, symbol!("mint_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_mint_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::TokenExists))
+    );
+    assert_eq!(token.supply(), 1);
+    assert_eq!(token.balance(&user_id), 1);
+    assert_eq!(token.nonce(&admin_id), nonce);
+    assert_eq!(token.client().try_owner(&1), Err(Ok(Error::TokenNotFound)));
+
+    let ids = vec![&env, 1i128, 2, 1];
+    let (auth, nonce) = token.auth(&admin, symbol!("mint_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_mint_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::DuplicateId))
+    );
+
+    let ids = vec![&env, 1i128, 2];
+    let (auth, nonce) = token.auth(&admin, symbol!("mint_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_mint_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::LengthMismatch))
+    );
+
+    // Failing while the batch is applied also rolls back the tokens already written.
+    token.reveal(&admin, &to_bytes(&env, TOKEN_SEED));
+    let to = vec![&env, user_id.clone(), user_id.clone()];
+    let ids = vec![&env, 1i128, 2];
+    let (auth, nonce) = token.auth(&admin, symbol!("mint_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_mint_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::AlreadyRevealed))
+    );
+    assert_eq!(token.supply(), 1);
+    assert_eq!(token.balance(&user_id), 1);
+    assert_eq!(
+        token.client().try_token_at(&1),
+        Err(Ok(Error::IndexOutOfBounds))
+    );
+}
+
+#[test]
+fn test_xfer_batch() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+    let user3_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint_batch(
+        &admin,
+        &vec![&env, user1_id.clone(), user1_id.clone(), user1_id.clone()],
+        &vec![&env, 1i128, 2, 3],
+    );
+    token.appr(&user1, &admin_id, &2);
+
+    token.xfer_batch(
+        &user1,
+        &vec![&env, user2_id.clone(), user3_id.clone()],
+        &vec![&env, 1i128, 2],
+    );
+    assert_eq!(token.nonce(&user1_id), 2);
+    assert_eq!(token.owner(&1), user2_id);
+    assert_eq!(token.owner(&2), user3_id);
+    assert_eq!(token.balance(&user1_id), 1);
+    assert_eq!(token.get_appr(&2), zero_address(&env));
+
+    // A token not owned by the sender at the end of the batch rolls back every transfer.
+    let to = vec![&env, user2_id.clone(), user2_id.clone()];
+    let ids = vec![&env, 3i128, 1];
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_xfer_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::NotOwner))
+    );
+    assert_eq!(token.owner(&3), user1_id);
+    assert_eq!(token.balance(&user1_id), 1);
+
+    let ids = vec![&env, 3i128, 3];
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer_batch"), (&to, &ids));
+    assert_eq!(
+        token.client().try_xfer_batch(&auth, &nonce, &to, &ids),
+        Err(Ok(Error::DuplicateId))
+    );
+}
+
+#[test]
+fn test_safe_xfer_to_receiver() {
+    let (env, token) = Token::create();
+    let receiver_id = env.register_contract(None, Receiver);
+    let receiver = Identifier::Contract(receiver_id.clone());
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    let data = to_bytes(&env, "gift");
+    token.safe_xfer(&user, &receiver, &1, &data);
+    assert_eq!(token.owner(&1), receiver);
+    assert_eq!(
+        ReceiverClient::new(&env, &receiver_id).received(),
+        (user_id.clone(), user_id, 1, data)
+    );
+}
+
+#[test]
+fn test_safe_xfer_to_account() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+
+    token.safe_xfer(&user1, &user2_id, &1, &Bytes::new(&env));
+    assert_eq!(token.owner(&1), user2_id);
+}
+
+#[test]
+fn test_safe_xfer_rejected() {
+    let (env, token) = Token::create();
+    let receiver_id = env.register_contract(None, Receiver);
+    let receiver = Identifier::Contract(receiver_id.clone());
+    ReceiverClient::new(&env, &receiver_id).set_accept(&false);
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    let data = Bytes::new(&env);
+    let (auth, nonce) = token.auth(&user, symbol!("safe_xfer"), (&receiver, &1i128, &data));
+    assert_eq!(
+        token
+            .client()
+            .try_safe_xfer(&auth, &nonce, &receiver, &1, &data),
+        Err(Ok(Error::NotReceived))
+    );
+    assert_eq!(token.owner(&1), user_id);
+    assert_eq!(token.balance(&receiver), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_safe_xfer_to_non_receiver() {
+    let (env, token) = Token::create();
+    // The token contract does not implement the hook, so it cannot receive tokens.
+    let other = Identifier::Contract(register_contract(&env));
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+
+    token.safe_xfer(&user, &other, &1, &Bytes::new(&env));
+}
+
+#[test]
+fn test_safe_from() {
+    let (env, token) = Token::create();
+    let receiver_id = env.register_contract(None, Receiver);
+    let receiver = Identifier::Contract(receiver_id.clone());
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let operator = generate_keypair();
+    let operator_id = to_ed25519(&env, &operator);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &2);
+
+    let data = Bytes::new(&env);
+    let (auth, nonce) = token.auth(
+        &operator,
+        symbol!("safe_from"),
+        (&user_id, &receiver, &1i128, &data),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_safe_from(&auth, &user_id, &receiver, &nonce, &1, &data),
+        Err(Ok(Error::NotApproved))
+    );
+
+    token.appr_all(&user, &operator_id, &true);
+    token.safe_from(&operator, &user_id, &receiver, &1, &data);
+    assert_eq!(token.owner(&1), receiver);
+    assert_eq!(
+        ReceiverClient::new(&env, &receiver_id).received(),
+        (operator_id, user_id.clone(), 1, data.clone())
+    );
+
+    ReceiverClient::new(&env, &receiver_id).set_accept(&false);
+    let (auth, nonce) = token.auth(
+        &operator,
+        symbol!("safe_from"),
+        (&user_id, &receiver, &2i128, &data),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_safe_from(&auth, &user_id, &receiver, &nonce, &2, &data),
+        Err(Ok(Error::NotReceived))
+    );
+    assert_eq!(token.owner(&2), user_id);
+}
+
+#[test]
+fn test_pause() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+    assert!(!token.paused());
+
+    token.pause(&admin);
+    assert!(token.paused());
+
+    let (auth, nonce) = token.auth(&user1, symbol!("xfer"), (&user2_id, &1i128));
+    assert_eq!(
+        token.client().try_xfer(&auth, &nonce, &user2_id, &1),
+        Err(Ok(Error::Paused))
+    );
+    assert_eq!(token.owner(&1), user1_id);
+
+    // Admin settings and views keep working while paused.
+    token.set_base(
+        &admin,
+        &to_bytes(&env, "ipfs://base/"),
+        &to_bytes(&env, ".json"),
+    );
+    assert_eq!(token.balance(&user1_id), 1);
+
+    token.unpause(&admin);
+    assert!(!token.paused());
+
+    token.xfer(&user1, &user2_id, &1);
+    assert_eq!(token.owner(&1), user2_id);
+}
+
+#[test]
+fn test_pause_non_pauser() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+
+    token.initialize(&admin_id);
+
+    let (auth, nonce) = token.auth(&user, symbol!("pause"), ());
+    assert_eq!(
+        token.client().try_pause(&auth, &nonce),
+        Err(Ok(Error::MissingRole))
+    );
+
+    token.pause(&admin);
+    let (auth, nonce) = token.auth(&user, symbol!("unpause"), ());
+    assert_eq!(
+        token.client().try_unpause(&auth, &nonce),
+        Err(Ok(Error::MissingRole))
+    );
+    assert!(token.paused());
+}
+
+#[test]
+fn test_pause_all_paths() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let other_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+    token.pause(&admin);
+
+    // The pause is checked before the signature, so one signature serves every call.
+    let (auth, nonce) = token.auth(&user, symbol!("xfer"), (&other_id, &1i128));
+    let client = token.client();
+    let price = Price {
+        token: BytesN::from_array(&env, &[0; 32]),
+        amount: 100,
+    };
+    let data = Bytes::new(&env);
+    let ids = vec![&env, 2i128];
+    let to = vec![&env, other_id.clone()];
+    let paused = Err(Ok(Error::Paused));
+
+    assert_eq!(client.try_xfer(&auth, &nonce, &other_id, &1), paused);
+    assert_eq!(client.try_xfer_batch(&auth, &nonce, &to, &ids), paused);
+    assert_eq!(
+        client.try_xfer_from(&auth, &user_id, &other_id, &nonce, &1),
+        paused
+    );
+    assert_eq!(
+        client.try_xfer_pay(&auth, &nonce, &auth, &nonce, &1, &price),
+        paused
+    );
+    assert_eq!(
+        client.try_safe_xfer(&auth, &nonce, &other_id, &1, &data),
+        paused
+    );
+    assert_eq!(
+        client.try_safe_from(&auth, &user_id, &other_id, &nonce, &1, &data),
+        paused
+    );
+    assert_eq!(client.try_appr(&auth, &nonce, &other_id, &1, &None), paused);
+    assert_eq!(
+        client.try_appr_all(&auth, &nonce, &other_id, &true, &None),
+        paused
+    );
+    assert_eq!(client.try_list(&auth, &nonce, &1, &price), paused);
+    assert_eq!(client.try_buy(&auth, &nonce, &1, &price), paused);
+    assert_eq!(
+        client.try_start_auc(&auth, &nonce, &1, &price, &100, &1),
+        paused
+    );
+    assert_eq!(client.try_bid(&auth, &nonce, &1, &100), paused);
+    assert_eq!(client.try_settle(&1), paused);
+    assert_eq!(
+        client.try_mint(&auth, &nonce, &other_id, &2, &None, &None),
+        paused
+    );
+    assert_eq!(client.try_mint_batch(&auth, &nonce, &to, &ids), paused);
+    assert_eq!(
+        client
+            .with_source_account(&env.accounts().generate())
+            .try_mint_next(),
+        paused
+    );
+    assert_eq!(client.try_mint_paid(&auth, &nonce), paused);
+    assert_eq!(
+        client.try_mint_pre(&auth, &nonce, &1, &Vec::new(&env)),
+        paused
+    );
+    assert_eq!(client.try_burn(&auth, &nonce, &1), paused);
+
+    assert_eq!(token.owner(&1), user_id);
+    assert_eq!(token.nonce(&user_id), 0);
+}
+
+#[test]
+fn test_minter_role() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let minter = generate_keypair();
+    let minter_id = to_ed25519(&env, &minter);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    assert!(token.has_role(&Role::Minter, &admin_id));
+    assert!(!token.has_role(&Role::Minter, &minter_id));
+
+    let (auth, nonce) = token.auth(&minter, symbol!("mint"), (&user_id, &1i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+
+    token.grant_role(&admin, &Role::Minter, &minter_id);
+    assert!(token.has_role(&Role::Minter, &minter_id));
+    assert!(!token.has_role(&Role::Burner, &minter_id));
+    token.mint(&minter, &user_id, &1);
+    assert_eq!(token.owner(&1), user_id);
+
+    // Minting does not give the minter any admin rights.
+    let (auth, nonce) = token.auth(&minter, symbol!("prop_admin"), (&minter_id,));
+    assert_eq!(
+        token.client().try_prop_admin(&auth, &nonce, &minter_id),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    token.drop_role(&admin, &Role::Minter, &minter_id);
+    assert!(!token.has_role(&Role::Minter, &minter_id));
+    let (auth, nonce) = token.auth(&minter, symbol!("mint"), (&user_id, &2i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &2, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+}
+
+#[test]
+fn test_burner_role() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let burner = generate_keypair();
+    let burner_id = to_ed25519(&env, &burner);
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user_id, &1);
+    token.mint(&admin, &user_id, &2);
+
+    let (auth, nonce) = token.auth(&burner, symbol!("burn"), (&1i128,));
+    assert_eq!(
+        token.client().try_burn(&auth, &nonce, &1),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    token.grant_role(&admin, &Role::Burner, &burner_id);
+    token.burn(&burner, &1);
+    assert_eq!(token.balance(&user_id), 1);
+
+    token.renounce(&burner, &Role::Burner);
+    assert!(!token.has_role(&Role::Burner, &burner_id));
+    let (auth, nonce) = token.auth(&burner, symbol!("burn"), (&2i128,));
+    assert_eq!(
+        token.client().try_burn(&auth, &nonce, &2),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_metadata_and_pauser_roles() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let manager = generate_keypair();
+    let manager_id = to_ed25519(&env, &manager);
+    let pauser = generate_keypair();
+    let pauser_id = to_ed25519(&env, &pauser);
+
+    token.initialize(&admin_id);
+    token.grant_role(&admin, &Role::Metadata, &manager_id);
+    token.grant_role(&admin, &Role::Pauser, &pauser_id);
+
+    let base = to_bytes(&env, "ipfs://base/");
+    let suffix = to_bytes(&env, ".json");
+    token.set_base(&manager, &base, &suffix);
+    assert_eq!(token.client().base_uri(), base);
+
+    let (auth, nonce) = token.auth(&pauser, symbol!("set_base"), (&base, &suffix));
+    assert_eq!(
+        token.client().try_set_base(&auth, &nonce, &base, &suffix),
+        Err(Ok(Error::MissingRole))
+    );
+
+    token.pause(&pauser);
+    assert!(token.paused());
+    let (auth, nonce) = token.auth(&manager, symbol!("unpause"), ());
+    assert_eq!(
+        token.client().try_unpause(&auth, &nonce),
+        Err(Ok(Error::MissingRole))
+    );
+    token.unpause(&pauser);
+    assert!(!token.paused());
+}
+
+#[test]
+fn test_role_management() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    assert!(token.has_role(&Role::Admin, &admin_id));
+    assert!(!token.has_role(&Role::Admin, &user_id));
+
+    let (auth, nonce) = token.auth(&user, symbol!("grant_role"), (Role::Minter, &user_id));
+    assert_eq!(
+        token
+            .client()
+            .try_grant_role(&auth, &nonce, &Role::Minter, &user_id),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    let (auth, nonce) = token.auth(&admin, symbol!("grant_role"), (Role::Admin, &user_id));
+    assert_eq!(
+        token
+            .client()
+            .try_grant_role(&auth, &nonce, &Role::Admin, &user_id),
+        Err(Ok(Error::InvalidRole))
+    );
+
+    token.grant_role(&admin, &Role::Minter, &user_id);
+    let (auth, nonce) = token.auth(&user, symbol!("drop_role"), (Role::Minter, &user_id));
+    assert_eq!(
+        token
+            .client()
+            .try_drop_role(&auth, &nonce, &Role::Minter, &user_id),
+        Err(Ok(Error::NotAdmin))
+    );
+    assert!(token.has_role(&Role::Minter, &user_id));
+
+    let events = env.events().all();
+    let event = events.last().unwrap().unwrap();
+    assert_eq!(
+        event.1,
+        (symbol!("grant_role"), admin_id, user_id).into_val(&env)
+    );
+}
+
+#[test]
+fn test_admin_handover() {
+    let (env, token) = Token::create();
+
+    let admin1 = generate_keypair();
+    let admin1_id = to_ed25519(&env, &admin1);
+    let admin2 = generate_keypair();
+    let admin2_id = to_ed25519(&env, &admin2);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin1_id);
+    assert_eq!(token.pend_admin(), None);
+
+    token.prop_admin(&admin1, &admin2_id);
+    assert_eq!(token.pend_admin(), Some(admin2_id.clone()));
+    assert_eq!(token.client().admin(), admin1_id);
+
+    let (auth, nonce) = token.auth(&user, symbol!("accept_adm"), ());
+    assert_eq!(
+        token.client().try_accept_adm(&auth, &nonce),
+        Err(Ok(Error::NotPending))
+    );
+
+    token.accept_adm(&admin2);
+    assert_eq!(token.client().admin(), admin2_id);
+    assert_eq!(token.pend_admin(), None);
+
+    token.mint(&admin2, &user_id, &1);
+    let (auth, nonce) = token.auth(&admin1, symbol!("mint"), (&user_id, &2i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &2, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+}
+
+#[test]
+fn test_admin_handover_cancelled() {
+    let (env, token) = Token::create();
+
+    let admin1 = generate_keypair();
+    let admin1_id = to_ed25519(&env, &admin1);
+    let admin2 = generate_keypair();
+    let admin2_id = to_ed25519(&env, &admin2);
+
+    token.initialize(&admin1_id);
+
+    let (auth, nonce) = token.auth(&admin1, symbol!("cancel_adm"), ());
+    assert_eq!(
+        token.client().try_cancel_adm(&auth, &nonce),
+        Err(Ok(Error::NotPending))
+    );
+
+    token.prop_admin(&admin1, &admin2_id);
+    token.cancel_adm(&admin1);
+    assert_eq!(token.pend_admin(), None);
+
+    let (auth, nonce) = token.auth(&admin2, symbol!("accept_adm"), ());
+    assert_eq!(
+        token.client().try_accept_adm(&auth, &nonce),
+        Err(Ok(Error::NotPending))
+    );
+    assert_eq!(token.client().admin(), admin1_id);
+}
+
+#[test]
+fn test_renounce_admin() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.prop_admin(&admin, &user_id);
+
+    let (auth, nonce) = token.auth(&user, symbol!("renounce"), (Role::Admin,));
+    assert_eq!(
+        token.client().try_renounce(&auth, &nonce, &Role::Admin),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    token.renounce(&admin, &Role::Admin);
+    assert_eq!(token.client().admin(), zero_address(&env));
+    assert_eq!(token.pend_admin(), None);
+    assert!(!token.has_role(&Role::Minter, &admin_id));
+
+    let (auth, nonce) = token.auth(&admin, symbol!("mint"), (&user_id, &1i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+    let (auth, nonce) = token.auth(&user, symbol!("accept_adm"), ());
+    assert_eq!(
+        token.client().try_accept_adm(&auth, &nonce),
+        Err(Ok(Error::NotPending))
+    );
+    assert_eq!(
+        token.client().try_initialize(
+            &user_id,
+            &to_bytes(&env, TOKEN_NAME),
+            &to_bytes(&env, TOKEN_SYMBOL),
+            &env.crypto().sha256(&to_bytes(&env, TOKEN_SEED))
+        ),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+fn signer_set(env: &Env, signers: &[(&Keypair, u32)], threshold: u32) -> Signers {
+    let mut set = Vec::new(env);
+    for (signer, weight) in signers {
+        set.push_back(Signer {
+            id: to_ed25519(env, signer),
+            weight: *weight,
+        });
+    }
+    Signers {
+        signers: set,
+        threshold,
+    }
+}
+
+#[test]
+fn test_multisig_admin() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let signer1 = generate_keypair();
+    let signer2 = generate_keypair();
+    let signer3 = generate_keypair();
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    let signers = signer_set(&env, &[(&signer1, 2), (&signer2, 1), (&signer3, 1)], 3);
+    token.set_sigs(&admin, &signers);
+    assert_eq!(token.signers(), Some(signers));
+    assert_eq!(token.client().admin(), zero_address(&env));
+
+    // The former administrator no longer holds any admin rights.
+    let (auth, nonce) = token.auth(&admin, symbol!("mint"), (&user_id, &1i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+
+    token.msig_mint(&[&signer1, &signer2], &user_id, &1);
+    token.msig_mint(&[&signer2, &signer3, &signer1], &user_id, &2);
+    assert_eq!(token.balance(&user_id), 2);
+    assert_eq!(token.client().sign_nonce(), 2);
+
+    token.msig_burn(&[&signer1, &signer3], &1);
+    assert_eq!(token.balance(&user_id), 1);
+
+    token.msig_admin(&[&signer1, &signer2], &admin_id);
+    assert_eq!(token.client().admin(), admin_id);
+    assert_eq!(token.signers(), None);
+    token.mint(&admin, &user_id, &3);
+}
+
+#[test]
+fn test_multisig_drop_role() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let minter = generate_keypair();
+    let minter_id = to_ed25519(&env, &minter);
+    let signer1 = generate_keypair();
+    let signer2 = generate_keypair();
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.grant_role(&admin, &Role::Minter, &minter_id);
+    let signers = signer_set(&env, &[(&signer1, 1), (&signer2, 1)], 2);
+    token.set_sigs(&admin, &signers);
+
+    // Roles survive the switch, and only the signer set can revoke them afterwards.
+    token.mint(&minter, &user_id, &1);
+    let (auth, nonce) = token.auth(&admin, symbol!("drop_role"), (Role::Minter, &minter_id));
+    assert_eq!(
+        token
+            .client()
+            .try_drop_role(&auth, &nonce, &Role::Minter, &minter_id),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    let (sigs, nonce) = token.msig_auth(
+        &[&signer1],
+        symbol!("msig_drop"),
+        (Role::Minter, &minter_id),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_msig_drop(&sigs, &nonce, &Role::Minter, &minter_id),
+        Err(Ok(Error::BelowThreshold))
+    );
+
+    token.msig_drop(&[&signer1, &signer2], &Role::Minter, &minter_id);
+    assert!(!token.has_role(&Role::Minter, &minter_id));
+    let (auth, nonce) = token.auth(&minter, symbol!("mint"), (&user_id, &2i128, (), ()));
+    assert_eq!(
+        token
+            .client()
+            .try_mint(&auth, &nonce, &user_id, &2, &None, &None),
+        Err(Ok(Error::MissingRole))
+    );
+}
+
+#[test]
+fn test_multisig_below_threshold() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let signer1 = generate_keypair();
+    let signer2 = generate_keypair();
+    let outsider = generate_keypair();
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    token.set_sigs(
+        &admin,
+        &signer_set(&env, &[(&signer1, 1), (&signer2, 1)], 2),
+    );
+
+    let (sigs, nonce) = token.msig_auth(
+        &[&signer1],
+        symbol!("msig_mint"),
+        (&user_id, &1i128, (), ()),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_msig_mint(&sigs, &nonce, &user_id, &1, &None, &None),
+        Err(Ok(Error::BelowThreshold))
+    );
+
+    token.msig_mint(&[&signer1, &signer2], &user_id, &1);
+    let (sigs, nonce) = token.msig_auth(&[&signer1, &outsider], symbol!("msig_burn"), (&1i128,));
+    assert_eq!(
+        token.client().try_msig_burn(&sigs, &nonce, &1),
+        Err(Ok(Error::NotSigner))
+    );
+    assert_eq!(token.owner(&1), user_id);
+}
+
+#[test]
+fn test_multisig_duplicate_signer() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let signer1 = generate_keypair();
+    let signer2 = generate_keypair();
+    let user_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+
+    let signers = signer_set(&env, &[(&signer1, 1), (&signer1, 1)], 2);
+    let (auth, nonce) = token.auth(&admin, symbol!("set_sigs"), (&signers,));
+    assert_eq!(
+        token.client().try_set_sigs(&auth, &nonce, &signers),
+        Err(Ok(Error::DuplicateSigner))
+    );
+
+    let signers = signer_set(&env, &[(&signer1, 1), (&signer2, 1)], 3);
+    assert_eq!(
+        token.client().try_set_sigs(&auth, &nonce, &signers),
+        Err(Ok(Error::InvalidSigners))
+    );
+
+    token.set_sigs(
+        &admin,
+        &signer_set(&env, &[(&signer1, 1), (&signer2, 1)], 2),
+    );
+
+    // Signing twice does not count the weight of a signer twice.
+    let (sigs, nonce) = token.msig_auth(&[&signer1, &signer1], symbol!("msig_admin"), (&user_id,));
+    assert_eq!(
+        token.client().try_msig_admin(&sigs, &nonce, &user_id),
+        Err(Ok(Error::DuplicateSigner))
+    );
+    assert_eq!(token.signers().unwrap().threshold, 2);
+}
+
+#[test]
+fn test_timelock() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let receiver_id = to_ed25519(&env, &generate_keypair());
+
+    token.initialize(&admin_id);
+    set_timestamp(&env, 1_000);
+
+    // Without a delay, queued operations are ready right away.
+    let id = token.queue(&admin, &Operation::SetDelay(100));
+    token.execute(&id);
+    assert_eq!(token.client().delay(), 100);
+
+    let royalty = Royalty {
+        receiver: receiver_id.clone(),
+        bps: 500,
+    };
+    let (auth, nonce) = token.auth(&admin, symbol!("set_royal"), (&royalty,));
+    assert_eq!(
+        token.client().try_set_royal(&auth, &nonce, &royalty),
+        Err(Ok(Error::Timelocked))
+    );
+
+    let op = Operation::SetRoyal(royalty.clone());
+    let id = token.queue(&admin, &op);
+    assert_eq!(token.queued(), vec![&env, QueuedOp { id, op, eta: 1_100 }]);
+
+    set_timestamp(&env, 1_099);
+    assert_eq!(token.client().try_execute(&id), Err(Ok(Error::NotReady)));
+
+    set_timestamp(&env, 1_100);
+    token.execute(&id);
+    assert_eq!(token.client().get_royal(), Some(royalty));
+    assert_eq!(token.queued(), Vec::new(&env));
+    assert_eq!(token.client().try_execute(&id), Err(Ok(Error::NotQueued)));
+
+    let base = to_bytes(&env, "ipfs://base/");
+    let suffix = to_bytes(&env, ".json");
+    let id = token.queue(
+        &admin,
+        &Operation::SetBase(BaseUri {
+            base: base.clone(),
+            suffix,
+        }),
+    );
+    set_timestamp(&env, 1_200);
+    token.execute(&id);
+    assert_eq!(token.client().base_uri(), base);
+}
+
+#[test]
+fn test_timelock_delay_bounds() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+
+    token.initialize(&admin_id);
+
+    let op = Operation::SetDelay(MAX_DELAY + 1);
+    let (auth, nonce) = token.auth(&admin, symbol!("queue"), (&op,));
+    assert_eq!(
+        token.client().try_queue(&auth, &nonce, &op),
+        Err(Ok(Error::InvalidDelay))
+    );
+
+    let id = token.queue(&admin, &Operation::SetDelay(MAX_DELAY));
+    token.execute(&id);
+    assert_eq!(token.client().delay(), MAX_DELAY);
+
+    // An eta past the largest timestamp is rejected instead of wrapping around.
+    set_timestamp(&env, u64::MAX - MAX_DELAY + 1);
+    let op = Operation::SetDelay(0);
+    let (auth, nonce) = token.auth(&admin, symbol!("queue"), (&op,));
+    assert_eq!(
+        token.client().try_queue(&auth, &nonce, &op),
+        Err(Ok(Error::InvalidDelay))
+    );
+}
+
+#[test]
+fn test_timelock_cancel() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    let id = token.queue(&admin, &Operation::SetDelay(100));
+    token.execute(&id);
+
+    let (auth, nonce) = token.auth(&admin, symbol!("prop_admin"), (&user_id,));
+    assert_eq!(
+        token.client().try_prop_admin(&auth, &nonce, &user_id),
+        Err(Ok(Error::Timelocked))
+    );
+
+    let id = token.queue(&admin, &Operation::SetAdmin(user_id.clone()));
+    let (auth, nonce) = token.auth(&user, symbol!("cancel"), (&id,));
+    assert_eq!(
+        token.client().try_cancel(&auth, &nonce, &id),
+        Err(Ok(Error::NotAdmin))
+    );
+
+    token.cancel(&admin, &id);
+    assert_eq!(token.queued(), Vec::new(&env));
+
+    set_timestamp(&env, 100);
+    assert_eq!(token.client().try_execute(&id), Err(Ok(Error::NotQueued)));
+    assert_eq!(token.client().admin(), admin_id);
+}
+
+#[test]
+fn test_timelock_admin_handover() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    let id = token.queue(&admin, &Operation::SetAdmin(user_id.clone()));
+    token.execute(&id);
+
+    // The queued change only proposes the administrator.
+    assert_eq!(token.client().admin(), admin_id);
+    assert_eq!(token.pend_admin(), Some(user_id.clone()));
+
+    token.accept_adm(&user);
+    assert_eq!(token.client().admin(), user_id);
+    assert_eq!(token.pend_admin(), None);
+}
+
+#[test]
+fn test_timelock_cleared_on_handover() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+    let signer = generate_keypair();
+
+    token.initialize(&admin_id);
+    let royalty = Royalty {
+        receiver: admin_id.clone(),
+        bps: 500,
+    };
+
+    // Operations queued by the previous administrator are dropped with the handover.
+    let id = token.queue(&admin, &Operation::SetRoyal(royalty.clone()));
+    token.prop_admin(&admin, &user_id);
+    token.accept_adm(&user);
+    assert_eq!(token.queued(), Vec::new(&env));
+    assert_eq!(token.client().try_execute(&id), Err(Ok(Error::NotQueued)));
+
+    // The same holds when a signer set takes over.
+    let id = token.queue(&user, &Operation::SetRoyal(royalty));
+    token.set_sigs(&user, &signer_set(&env, &[(&signer, 1)], 1));
+    assert_eq!(token.queued(), Vec::new(&env));
+    assert_eq!(token.client().try_execute(&id), Err(Ok(Error::NotAdmin)));
+    assert_eq!(token.client().get_royal(), None);
+}
+
+#[test]
+fn test_timelock_clears_pending_admin() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user = generate_keypair();
+    let user_id = to_ed25519(&env, &user);
+
+    token.initialize(&admin_id);
+    token.prop_admin(&admin, &user_id);
+
+    // Turning the delay on drops the proposal, so it cannot be accepted around the queue.
+    let id = token.queue(&admin, &Operation::SetDelay(100));
+    token.execute(&id);
+    assert_eq!(token.client().pend_admin(), None);
+
+    let (auth, nonce) = token.auth(&user, symbol!("accept_adm"), ());
+    assert_eq!(
+        token.client().try_accept_adm(&auth, &nonce),
+        Err(Ok(Error::NotPending))
+    );
+    assert_eq!(token.client().admin(), admin_id);
+}
+
+#[test]
+fn test_appr_expiry() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+    let operator = generate_keypair();
+    let operator_id = to_ed25519(&env, &operator);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+    token.mint(&admin, &user1_id, &2);
+    set_timestamp(&env, 1_000);
+
+    let (auth, nonce) = token.auth(&user1, symbol!("appr"), (&operator_id, &1i128, 1_000u64));
+    assert_eq!(
+        token
+            .client()
+            .try_appr(&auth, &nonce, &operator_id, &1, &Some(1_000)),
+        Err(Ok(Error::InvalidExpiry))
+    );
+
+    token.appr_until(&user1, &operator_id, &1, &Some(1_100));
+    token.appr_all_until(&user1, &operator_id, &true, &Some(1_200));
+    assert_eq!(token.get_appr(&1), operator_id);
+    assert!(token.is_appr(&user1_id, &operator_id));
+
+    set_timestamp(&env, 1_100);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+    assert!(token.is_appr(&user1_id, &operator_id));
+
+    // An expired approval counts as none, not as an approval of the zero address.
+    env.as_contract(&token.contract_id(), || {
+        assert_eq!(read_approval(&env, 1), None);
+    });
+
+    set_timestamp(&env, 1_200);
+    assert!(!token.is_appr(&user1_id, &operator_id));
+    let (auth, nonce) = token.auth(
+        &operator,
+        symbol!("xfer_from"),
+        (&user1_id, &user2_id, &2i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user1_id, &user2_id, &nonce, &2),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(token.owner(&2), user1_id);
+
+    // Revoking with an expiry that already passed still works.
+    token.appr_all(&user1, &operator_id, &true);
+    token.appr_all_until(&user1, &operator_id, &false, &Some(1_000));
+    assert!(!token.is_appr(&user1_id, &operator_id));
+}
+
+#[test]
+fn test_revoke() {
+    let (env, token) = Token::create();
+
+    let admin = generate_keypair();
+    let admin_id = to_ed25519(&env, &admin);
+    let user1 = generate_keypair();
+    let user1_id = to_ed25519(&env, &user1);
+    let user2_id = to_ed25519(&env, &generate_keypair());
+    let operator = generate_keypair();
+    let operator_id = to_ed25519(&env, &operator);
+
+    token.initialize(&admin_id);
+    token.mint(&admin, &user1_id, &1);
+
+    token.appr(&user1, &operator_id, &1);
+    token.revoke(&user1, &1);
+    assert_eq!(token.get_appr(&1), zero_address(&env));
+
+    let (auth, nonce) = token.auth(&operator, symbol!("revoke"), (&1i128,));
+    assert_eq!(
+        token.client().try_revoke(&auth, &nonce, &1),
+        Err(Ok(Error::NotOwner))
+    );
+
+    token.appr_all(&user1, &operator_id, &true);
+    token.revoke_all(&user1, &operator_id);
+    assert!(!token.is_appr(&user1_id, &operator_id));
+
+    let (auth, nonce) = token.auth(
+        &operator,
+        symbol!("xfer_from"),
+        (&user1_id, &user2_id, &1i128),
+    );
+    assert_eq!(
+        token
+            .client()
+            .try_xfer_from(&auth, &user1_id, &user2_id, &nonce, &1),
+        Err(Ok(Error::NotApproved))
+    );
 }
@@ -1,13 +1,21 @@
 #![cfg(any(test, feature = "testutils"))]
 
 use crate::contract::{NonFungibleToken, NonFungibleTokenClient};
+use crate::storage_types::{
+    Auction, Operation, Presale, Price, QueuedOp, Role, Royalty, Sale, Signers,
+};
 use ed25519_dalek::Keypair;
 use soroban_auth::{Ed25519Signature, Identifier, Signature, SignaturePayload, SignaturePayloadV0};
-use soroban_sdk::testutils::ed25519::Sign;
-use soroban_sdk::{symbol, AccountId, Bytes, BytesN, Env, IntoVal};
+use soroban_sdk::testutils::{ed25519::Sign, Accounts};
+use soroban_sdk::xdr::{AlphaNum4, Asset, AssetCode4};
+use soroban_sdk::{
+    contractclient, contractimpl, symbol, AccountId, Bytes, BytesN, Env, IntoVal, RawVal, Symbol,
+    Vec,
+};
 
 pub const TOKEN_NAME: &str = "Non Fungible Dogs";
 pub const TOKEN_SYMBOL: &str = "NFD";
+pub const TOKEN_SEED: &str = "Non Fungible Seed";
 
 pub fn register_contract(env: &Env) -> BytesN<32> {
     env.register_contract(None, NonFungibleToken {})
@@ -17,6 +25,183 @@ pub fn to_ed25519(env: &Env, kp: &Keypair) -> Identifier {
     Identifier::Ed25519(kp.public.to_bytes().into_val(env))
 }
 
+/// Sign the canonical payload for calling "name" on "contract_id" with "args" by "signer".
+pub fn sign(
+    env: &Env,
+    contract_id: &BytesN<32>,
+    signer: &Keypair,
+    name: Symbol,
+    args: impl IntoVal<Env, Vec<RawVal>>,
+) -> Signature {
+    let msg = SignaturePayload::V0(SignaturePayloadV0 {
+        name,
+        contract: contract_id.clone(),
+        network: env.ledger().network_passphrase(),
+        args: args.into_val(env),
+    });
+
+    Signature::Ed25519(Ed25519Signature {
+        public_key: signer.public.to_bytes().into_val(env),
+        signature: signer.sign(msg).unwrap().into_val(env),
+    })
+}
+
+// Signs as the all-zero public key, a small-order point: with s = 0 a signature passes
+// non-strict verification whenever its R equals [k](-A), which holds for one of the four
+// points of the subgroup about a quarter of the time.
+struct ZeroKey;
+
+impl ed25519_dalek::Signer<ed25519_dalek::Signature> for ZeroKey {
+    fn try_sign(
+        &self,
+        msg: &[u8],
+    ) -> Result<ed25519_dalek::Signature, ed25519_dalek::SignatureError> {
+        use ed25519_dalek::Verifier;
+
+        let public = ed25519_dalek::PublicKey::from_bytes(&[0; 32])?;
+        let mut minus_two = [0xff; 32];
+        minus_two[0] = 0xec;
+        minus_two[31] = 0x7f;
+        let mut minus_one = [0; 32];
+        minus_one[31] = 0x80;
+        let mut identity = [0; 32];
+        identity[0] = 1;
+        for r in [identity, [0; 32], minus_one, minus_two] {
+            let mut bytes = [0; 64];
+            bytes[..32].copy_from_slice(&r);
+            let signature = ed25519_dalek::Signature::from_bytes(&bytes)?;
+            if public.verify(msg, &signature).is_ok() {
+                return Ok(signature);
+            }
+        }
+        Err(ed25519_dalek::SignatureError::new())
+    }
+}
+
+/// Forge a signature by the zero address for calling "name" on "contract_id" with "args",
+/// prefixed with the zero address and nonce 0, if one exists for this payload.
+pub fn forge_zero_key(
+    env: &Env,
+    contract_id: &BytesN<32>,
+    name: Symbol,
+    args: impl IntoVal<Env, Vec<RawVal>>,
+) -> Option<Signature> {
+    let public_key = BytesN::from_array(env, &[0; 32]);
+    let mut payload: Vec<RawVal> = (Identifier::Ed25519(public_key.clone()), 0i128).into_val(env);
+    payload.append(&args.into_val(env));
+
+    let msg = SignaturePayload::V0(SignaturePayloadV0 {
+        name,
+        contract: contract_id.clone(),
+        network: env.ledger().network_passphrase(),
+        args: payload,
+    });
+
+    let signature = ZeroKey.sign(msg).ok()?;
+    Some(Signature::Ed25519(Ed25519Signature {
+        public_key,
+        signature: signature.into_val(env),
+    }))
+}
+
+// The parts of the standard token interface needed to fund buyers in tests.
+#[allow(dead_code)]
+#[contractclient(name = "PaymentTokenClient")]
+pub trait PaymentTokenInterface {
+    fn nonce(env: Env, id: Identifier) -> i128;
+    fn incr_allow(env: Env, from: Signature, nonce: i128, spender: Identifier, amount: i128);
+    fn balance(env: Env, id: Identifier) -> i128;
+    fn mint(env: Env, admin: Signature, nonce: i128, to: Identifier, amount: i128);
+}
+
+/// A Stellar asset contract used as the payment token, administered by its issuer account.
+pub struct PaymentToken {
+    env: Env,
+    contract_id: BytesN<32>,
+    issuer: AccountId,
+}
+
+impl PaymentToken {
+    pub fn create(env: &Env) -> Self {
+        let issuer = env.accounts().generate_and_create();
+        let asset = Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(*b"USDC"),
+            issuer: (&issuer).try_into().unwrap(),
+        });
+        let contract_id = env.register_stellar_asset_contract(asset);
+        Self {
+            env: env.clone(),
+            contract_id,
+            issuer,
+        }
+    }
+
+    pub fn price(&self, amount: i128) -> Price {
+        Price {
+            token: self.contract_id.clone(),
+            amount,
+        }
+    }
+
+    pub fn client(&self) -> PaymentTokenClient {
+        PaymentTokenClient::new(&self.env, &self.contract_id)
+    }
+
+    pub fn balance(&self, id: &Identifier) -> i128 {
+        self.client().balance(id)
+    }
+
+    pub fn mint(&self, to: &Identifier, amount: &i128) {
+        self.client()
+            .with_source_account(&self.issuer)
+            .mint(&Signature::Invoker, &0, to, amount);
+    }
+
+    /// Allow the contract "spender" to spend "amount" of the funds of "from".
+    pub fn approve(&self, from: &Keypair, spender: &BytesN<32>, amount: &i128) {
+        let from_id = to_ed25519(&self.env, from);
+        let spender = Identifier::Contract(spender.clone());
+        let nonce = self.client().nonce(&from_id);
+        let auth = sign(
+            &self.env,
+            &self.contract_id,
+            from,
+            symbol!("incr_allow"),
+            (&from_id, &nonce, &spender, amount),
+        );
+        self.client().incr_allow(&auth, &nonce, &spender, amount);
+    }
+}
+
+/// A contract receiving tokens through safe transfers, accepting them unless told otherwise.
+pub struct Receiver;
+
+#[contractimpl]
+impl Receiver {
+    pub fn set_accept(env: Env, accept: bool) {
+        env.storage().set(symbol!("accept"), accept);
+    }
+
+    pub fn on_nft_rcv(
+        env: Env,
+        operator: Identifier,
+        from: Identifier,
+        id: i128,
+        data: Bytes,
+    ) -> Symbol {
+        env.storage()
+            .set(symbol!("received"), (operator, from, id, data));
+        match env.storage().get(symbol!("accept")) {
+            Some(Ok(false)) => symbol!("rejected"),
+            _ => symbol!("on_nft_rcv"),
+        }
+    }
+
+    pub fn received(env: Env) -> (Identifier, Identifier, i128, Bytes) {
+        env.storage().get_unchecked(symbol!("received")).unwrap()
+    }
+}
+
 pub struct Token {
     env: Env,
     contract_id: BytesN<32>,
@@ -37,10 +222,58 @@ impl Token {
         (env, token)
     }
 
+    /// Sign "name" with "args" by "signer", prefixed with its identifier and current nonce.
+    pub fn auth(
+        &self,
+        signer: &Keypair,
+        name: Symbol,
+        args: impl IntoVal<Env, Vec<RawVal>>,
+    ) -> (Signature, i128) {
+        let signer_id = to_ed25519(&self.env, signer);
+        let nonce = self.nonce(&signer_id);
+
+        let mut payload: Vec<RawVal> = (signer_id, nonce).into_val(&self.env);
+        payload.append(&args.into_val(&self.env));
+
+        let auth = sign(&self.env, &self.contract_id, signer, name, payload);
+        (auth, nonce)
+    }
+
+    // Signs with each of "signers" for the current nonce of the signer set.
+    pub fn msig_auth(
+        &self,
+        signers: &[&Keypair],
+        name: Symbol,
+        args: impl IntoVal<Env, Vec<RawVal>>,
+    ) -> (Vec<Signature>, i128) {
+        let nonce = NonFungibleTokenClient::new(&self.env, &self.contract_id).sign_nonce();
+        let args: Vec<RawVal> = args.into_val(&self.env);
+
+        let mut sigs = Vec::new(&self.env);
+        for signer in signers {
+            let signer_id = to_ed25519(&self.env, signer);
+            let mut payload: Vec<RawVal> = (signer_id, nonce).into_val(&self.env);
+            payload.append(&args);
+            sigs.push_back(sign(&self.env, &self.contract_id, signer, name, payload));
+        }
+        (sigs, nonce)
+    }
+
+    pub fn contract_id(&self) -> BytesN<32> {
+        self.contract_id.clone()
+    }
+
+    pub fn client(&self) -> NonFungibleTokenClient {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+    }
+
     pub fn initialize(&self, admin: &Identifier) {
         let name: Bytes = TOKEN_NAME.into_val(&self.env);
         let symbol: Bytes = TOKEN_SYMBOL.into_val(&self.env);
-        NonFungibleTokenClient::new(&self.env, &self.contract_id).initialize(admin, &name, &symbol);
+        let seed: Bytes = TOKEN_SEED.into_val(&self.env);
+        let seed_hash = self.env.crypto().sha256(&seed);
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .initialize(admin, &name, &symbol, &seed_hash);
     }
 
     pub fn nonce(&self, owner: &Identifier) -> i128 {
@@ -59,6 +292,57 @@ impl Token {
         NonFungibleTokenClient::new(&self.env, &self.contract_id).token_uri(id)
     }
 
+    pub fn base_uri(&self) -> Bytes {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).base_uri()
+    }
+
+    pub fn set_base(&self, admin: &Keypair, base: &Bytes, suffix: &Bytes) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_base"), (base, suffix));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .set_base(&auth, &nonce, base, suffix);
+    }
+
+    pub fn set_uri(&self, admin: &Keypair, id: &i128, uri: &Option<Bytes>) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_uri"), (id, uri));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_uri(&auth, &nonce, id, uri);
+    }
+
+    pub fn set_hidden(&self, admin: &Keypair, uri: &Bytes) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_hidden"), (uri,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_hidden(&auth, &nonce, uri);
+    }
+
+    pub fn reveal(&self, admin: &Keypair, seed: &Bytes) {
+        let (auth, nonce) = self.auth(admin, symbol!("reveal"), (seed,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).reveal(&auth, &nonce, seed);
+    }
+
+    pub fn uris(&self) -> Vec<Bytes> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).uris()
+    }
+
+    pub fn add_uri(&self, admin: &Keypair, uri: &Bytes) {
+        let (auth, nonce) = self.auth(admin, symbol!("add_uri"), (uri,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).add_uri(&auth, &nonce, uri);
+    }
+
+    pub fn remove_uri(&self, admin: &Keypair, uri: &Bytes) {
+        let (auth, nonce) = self.auth(admin, symbol!("remove_uri"), (uri,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).remove_uri(&auth, &nonce, uri);
+    }
+
+    pub fn supply(&self) -> i128 {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).supply()
+    }
+
+    pub fn token_at(&self, index: &i128) -> i128 {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).token_at(index)
+    }
+
+    pub fn token_of(&self, owner: &Identifier, index: &i128) -> i128 {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).token_of(owner, index)
+    }
+
     pub fn get_appr(&self, id: &i128) -> Identifier {
         NonFungibleTokenClient::new(&self.env, &self.contract_id).get_appr(id)
     }
@@ -68,98 +352,187 @@ impl Token {
     }
 
     pub fn appr(&self, owner: &Keypair, operator: &Identifier, id: &i128) {
-        let owner_id = to_ed25519(&self.env, owner);
-        let nonce = self.nonce(&owner_id);
-
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("appr"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (owner_id, &nonce, operator, id).into_val(&self.env),
-        });
-
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: BytesN::from_array(&self.env, &owner.public.to_bytes()),
-            signature: owner.sign(msg).unwrap().into_val(&self.env),
-        });
+        self.appr_until(owner, operator, id, &None);
+    }
 
-        NonFungibleTokenClient::new(&self.env, &self.contract_id).appr(&auth, &nonce, operator, id);
+    pub fn appr_until(
+        &self,
+        owner: &Keypair,
+        operator: &Identifier,
+        id: &i128,
+        expiry: &Option<u64>,
+    ) {
+        let (auth, nonce) = self.auth(owner, symbol!("appr"), (operator, id, expiry));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .appr(&auth, &nonce, operator, id, expiry);
     }
 
-    pub fn appr_all(&self, owner: &Keypair, operator: &Identifier) {
-        let owner_id = to_ed25519(&self.env, owner);
-        let nonce = self.nonce(&owner_id);
+    pub fn appr_all(&self, owner: &Keypair, operator: &Identifier, approved: &bool) {
+        self.appr_all_until(owner, operator, approved, &None);
+    }
 
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("appr_all"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (owner_id, &nonce, operator).into_val(&self.env),
-        });
+    pub fn appr_all_until(
+        &self,
+        owner: &Keypair,
+        operator: &Identifier,
+        approved: &bool,
+        expiry: &Option<u64>,
+    ) {
+        let (auth, nonce) = self.auth(owner, symbol!("appr_all"), (operator, approved, expiry));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .appr_all(&auth, &nonce, operator, approved, expiry);
+    }
 
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: BytesN::from_array(&self.env, &owner.public.to_bytes()),
-            signature: owner.sign(msg).unwrap().into_val(&self.env),
-        });
+    pub fn revoke(&self, owner: &Keypair, id: &i128) {
+        let (auth, nonce) = self.auth(owner, symbol!("revoke"), (id,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).revoke(&auth, &nonce, id);
+    }
 
+    pub fn revoke_all(&self, owner: &Keypair, operator: &Identifier) {
+        let (auth, nonce) = self.auth(owner, symbol!("revoke_all"), (operator,));
         NonFungibleTokenClient::new(&self.env, &self.contract_id)
-            .appr_all(&auth, &nonce, operator, &true);
+            .revoke_all(&auth, &nonce, operator);
     }
 
     pub fn xfer(&self, from: &Keypair, to: &Identifier, id: &i128) {
-        let from_id = to_ed25519(&self.env, from);
-        let nonce = self.nonce(&from_id);
+        let (auth, nonce) = self.auth(from, symbol!("xfer"), (to, id));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).xfer(&auth, &nonce, to, id);
+    }
 
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("xfer"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (from_id, &nonce, to, id).into_val(&self.env),
-        });
+    pub fn xfer_batch(&self, from: &Keypair, to: &Vec<Identifier>, ids: &Vec<i128>) {
+        let (auth, nonce) = self.auth(from, symbol!("xfer_batch"), (to, ids));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .xfer_batch(&auth, &nonce, to, ids);
+    }
 
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: BytesN::from_array(&self.env, &from.public.to_bytes()),
-            signature: from.sign(msg).unwrap().into_val(&self.env),
-        });
+    pub fn safe_xfer(&self, from: &Keypair, to: &Identifier, id: &i128, data: &Bytes) {
+        let (auth, nonce) = self.auth(from, symbol!("safe_xfer"), (to, id, data));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .safe_xfer(&auth, &nonce, to, id, data);
+    }
 
-        NonFungibleTokenClient::new(&self.env, &self.contract_id).xfer(&auth, &nonce, to, id);
+    pub fn safe_from(
+        &self,
+        spender: &Keypair,
+        from: &Identifier,
+        to: &Identifier,
+        id: &i128,
+        data: &Bytes,
+    ) {
+        let (auth, nonce) = self.auth(spender, symbol!("safe_from"), (from, to, id, data));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .safe_from(&auth, from, to, &nonce, id, data);
     }
 
     pub fn xfer_from(&self, spender: &Keypair, from: &Identifier, to: &Identifier, id: &i128) {
-        let spender_id = to_ed25519(&self.env, spender);
-        let nonce = self.nonce(&spender_id);
-
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("xfer_from"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (spender_id, &nonce, from, to, id).into_val(&self.env),
-        });
+        let (auth, nonce) = self.auth(spender, symbol!("xfer_from"), (from, to, id));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .xfer_from(&auth, &from, &to, &nonce, id);
+    }
 
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: spender.public.to_bytes().into_val(&self.env),
-            signature: spender.sign(msg).unwrap().into_val(&self.env),
-        });
+    pub fn xfer_pay(&self, from: &Keypair, to: &Keypair, id: &i128, price: &Price) {
+        let from_id = to_ed25519(&self.env, from);
+        let to_id = to_ed25519(&self.env, to);
+        let (from_auth, from_nonce) = self.auth(from, symbol!("xfer_pay"), (&to_id, id, price));
+        let (to_auth, to_nonce) = self.auth(to, symbol!("xfer_pay"), (&from_id, id, price));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).xfer_pay(
+            &from_auth,
+            &from_nonce,
+            &to_auth,
+            &to_nonce,
+            id,
+            price,
+        );
+    }
+
+    pub fn listing(&self, id: &i128) -> Option<Price> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).listing(id)
+    }
+
+    pub fn list(&self, owner: &Keypair, id: &i128, price: &Price) {
+        let (auth, nonce) = self.auth(owner, symbol!("list"), (id, price));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).list(&auth, &nonce, id, price);
+    }
+
+    pub fn delist(&self, owner: &Keypair, id: &i128) {
+        let (auth, nonce) = self.auth(owner, symbol!("delist"), (id,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).delist(&auth, &nonce, id);
+    }
 
+    pub fn buy(&self, buyer: &Keypair, id: &i128, price: &Price) {
+        let (auth, nonce) = self.auth(buyer, symbol!("buy"), (id, price));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).buy(&auth, &nonce, id, price);
+    }
+
+    pub fn auction(&self, id: &i128) -> Option<Auction> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).auction(id)
+    }
+
+    pub fn start_auc(
+        &self,
+        seller: &Keypair,
+        id: &i128,
+        reserve: &Price,
+        end: &u64,
+        increment: &i128,
+    ) {
+        let (auth, nonce) = self.auth(seller, symbol!("start_auc"), (id, reserve, end, increment));
         NonFungibleTokenClient::new(&self.env, &self.contract_id)
-            .xfer_from(&auth, &from, &to, &nonce, id);
+            .start_auc(&auth, &nonce, id, reserve, end, increment);
+    }
+
+    pub fn bid(&self, bidder: &Keypair, id: &i128, amount: &i128) {
+        let (auth, nonce) = self.auth(bidder, symbol!("bid"), (id, amount));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).bid(&auth, &nonce, id, amount);
+    }
+
+    pub fn settle(&self, id: &i128) {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).settle(id);
     }
 
     pub fn mint(&self, admin: &Keypair, to: &Identifier, id: &i128) {
-        let admin_id = to_ed25519(&self.env, admin);
-        let nonce = self.nonce(&admin_id);
-
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("mint"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (admin_id, &nonce, to, id).into_val(&self.env),
-        });
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: admin.public.to_bytes().into_val(&self.env),
-            signature: admin.sign(msg).unwrap().into_val(&self.env),
-        });
-        NonFungibleTokenClient::new(&self.env, &self.contract_id).mint(&auth, &nonce, to, id);
+        let (auth, nonce) = self.auth(
+            admin,
+            symbol!("mint"),
+            (to, id, None::<Bytes>, None::<Royalty>),
+        );
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .mint(&auth, &nonce, to, id, &None, &None);
+    }
+
+    pub fn mint_with_uri(&self, admin: &Keypair, to: &Identifier, id: &i128, uri: &Bytes) {
+        let uri = Some(uri.clone());
+        let (auth, nonce) = self.auth(admin, symbol!("mint"), (to, id, &uri, None::<Royalty>));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .mint(&auth, &nonce, to, id, &uri, &None);
+    }
+
+    pub fn mint_with_royalty(
+        &self,
+        admin: &Keypair,
+        to: &Identifier,
+        id: &i128,
+        royalty: &Royalty,
+    ) {
+        let royalty = Some(royalty.clone());
+        let (auth, nonce) = self.auth(admin, symbol!("mint"), (to, id, None::<Bytes>, &royalty));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .mint(&auth, &nonce, to, id, &None, &royalty);
+    }
+
+    pub fn royalty(&self, id: &i128, sale_price: &i128) -> (Identifier, i128) {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).royalty(id, sale_price)
+    }
+
+    pub fn set_royal(&self, admin: &Keypair, royalty: &Royalty) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_royal"), (royalty,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_royal(&auth, &nonce, royalty);
+    }
+
+    pub fn mint_batch(&self, admin: &Keypair, to: &Vec<Identifier>, ids: &Vec<i128>) {
+        let (auth, nonce) = self.auth(admin, symbol!("mint_batch"), (to, ids));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .mint_batch(&auth, &nonce, to, ids);
     }
 
     pub fn mint_next(&self, source_account: &AccountId) {
@@ -168,41 +541,145 @@ impl Token {
             .mint_next();
     }
 
-    pub fn burn(&self, admin: &Keypair, id: &i128) {
-        let from_id = to_ed25519(&self.env, admin);
-        let nonce = self.nonce(&from_id);
+    pub fn set_sale(&self, admin: &Keypair, sale: &Sale) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_sale"), (sale,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_sale(&auth, &nonce, sale);
+    }
 
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("burn"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (from_id, &nonce, id).into_val(&self.env),
-        });
+    pub fn mint_paid(&self, minter: &Keypair) {
+        let (auth, nonce) = self.auth(minter, symbol!("mint_paid"), ());
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).mint_paid(&auth, &nonce);
+    }
 
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: BytesN::from_array(&self.env, &admin.public.to_bytes()),
-            signature: admin.sign(msg).unwrap().into_val(&self.env),
-        });
+    pub fn set_pre(&self, admin: &Keypair, presale: &Presale) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_pre"), (presale,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_pre(&auth, &nonce, presale);
+    }
+
+    pub fn mint_pre(&self, minter: &Keypair, allowance: &i128, proof: &Vec<BytesN<32>>) {
+        let (auth, nonce) = self.auth(minter, symbol!("mint_pre"), (allowance, proof));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .mint_pre(&auth, &nonce, allowance, proof);
+    }
+
+    pub fn withdraw(&self, admin: &Keypair, token: &BytesN<32>, to: &Identifier) {
+        let (auth, nonce) = self.auth(admin, symbol!("withdraw"), (token, to));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .withdraw(&auth, &nonce, token, to);
+    }
 
+    pub fn burn(&self, spender: &Keypair, id: &i128) {
+        let (auth, nonce) = self.auth(spender, symbol!("burn"), (id,));
         NonFungibleTokenClient::new(&self.env, &self.contract_id).burn(&auth, &nonce, id);
     }
 
-    pub fn set_admin(&self, admin: &Keypair, new_admin: &Identifier) {
-        let admin_id = to_ed25519(&self.env, admin);
-        let nonce = self.nonce(&admin_id);
+    pub fn burned(&self) -> i128 {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).burned()
+    }
 
-        let msg = SignaturePayload::V0(SignaturePayloadV0 {
-            name: symbol!("set_admin"),
-            contract: self.contract_id.clone(),
-            network: self.env.ledger().network_passphrase(),
-            args: (admin_id, &nonce, new_admin).into_val(&self.env),
-        });
-        let auth = Signature::Ed25519(Ed25519Signature {
-            public_key: admin.public.to_bytes().into_val(&self.env),
-            signature: admin.sign(msg).unwrap().into_val(&self.env),
-        });
+    pub fn pend_admin(&self) -> Option<Identifier> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).pend_admin()
+    }
+
+    pub fn prop_admin(&self, admin: &Keypair, new_admin: &Identifier) {
+        let (auth, nonce) = self.auth(admin, symbol!("prop_admin"), (new_admin,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .prop_admin(&auth, &nonce, new_admin);
+    }
+
+    pub fn accept_adm(&self, new_admin: &Keypair) {
+        let (auth, nonce) = self.auth(new_admin, symbol!("accept_adm"), ());
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).accept_adm(&auth, &nonce);
+    }
+
+    pub fn cancel_adm(&self, admin: &Keypair) {
+        let (auth, nonce) = self.auth(admin, symbol!("cancel_adm"), ());
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).cancel_adm(&auth, &nonce);
+    }
+
+    pub fn signers(&self) -> Option<Signers> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).signers()
+    }
+
+    pub fn set_sigs(&self, admin: &Keypair, signers: &Signers) {
+        let (auth, nonce) = self.auth(admin, symbol!("set_sigs"), (signers,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).set_sigs(&auth, &nonce, signers);
+    }
+
+    pub fn msig_admin(&self, signers: &[&Keypair], new_admin: &Identifier) {
+        let (sigs, nonce) = self.msig_auth(signers, symbol!("msig_admin"), (new_admin,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .msig_admin(&sigs, &nonce, new_admin);
+    }
+
+    pub fn msig_drop(&self, signers: &[&Keypair], role: &Role, account: &Identifier) {
+        let (sigs, nonce) = self.msig_auth(signers, symbol!("msig_drop"), (role, account));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .msig_drop(&sigs, &nonce, role, account);
+    }
+
+    pub fn msig_mint(&self, signers: &[&Keypair], to: &Identifier, id: &i128) {
+        let (sigs, nonce) = self.msig_auth(signers, symbol!("msig_mint"), (to, id, (), ()));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .msig_mint(&sigs, &nonce, to, id, &None, &None);
+    }
+
+    pub fn msig_burn(&self, signers: &[&Keypair], id: &i128) {
+        let (sigs, nonce) = self.msig_auth(signers, symbol!("msig_burn"), (id,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).msig_burn(&sigs, &nonce, id);
+    }
+
+    pub fn queued(&self) -> Vec<QueuedOp> {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).queued()
+    }
+
+    pub fn queue(&self, admin: &Keypair, op: &Operation) -> u32 {
+        let (auth, nonce) = self.auth(admin, symbol!("queue"), (op,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).queue(&auth, &nonce, op)
+    }
+
+    pub fn execute(&self, id: &u32) {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).execute(id);
+    }
+
+    pub fn cancel(&self, admin: &Keypair, id: &u32) {
+        let (auth, nonce) = self.auth(admin, symbol!("cancel"), (id,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).cancel(&auth, &nonce, id);
+    }
+
+    pub fn has_role(&self, role: &Role, account: &Identifier) -> bool {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).has_role(role, account)
+    }
+
+    pub fn grant_role(&self, admin: &Keypair, role: &Role, account: &Identifier) {
+        let (auth, nonce) = self.auth(admin, symbol!("grant_role"), (role, account));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id)
+            .grant_role(&auth, &nonce, role, account);
+    }
+
+    pub fn drop_role(&self, admin: &Keypair, role: &Role, account: &Identifier) {
+        let (auth, nonce) = self.auth(admin, symbol!("drop_role"), (role, account));
         NonFungibleTokenClient::new(&self.env, &self.contract_id)
-            .set_admin(&auth, &nonce, new_admin);
+            .drop_role(&auth, &nonce, role, account);
+    }
+
+    pub fn renounce(&self, account: &Keypair, role: &Role) {
+        let (auth, nonce) = self.auth(account, symbol!("renounce"), (role,));
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).renounce(&auth, &nonce, role);
+    }
+
+    pub fn paused(&self) -> bool {
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).paused()
+    }
+
+    pub fn pause(&self, admin: &Keypair) {
+        let (auth, nonce) = self.auth(admin, symbol!("pause"), ());
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).pause(&auth, &nonce);
+    }
+
+    pub fn unpause(&self, admin: &Keypair) {
+        let (auth, nonce) = self.auth(admin, symbol!("unpause"), ());
+        NonFungibleTokenClient::new(&self.env, &self.contract_id).unpause(&auth, &nonce);
     }
 
     pub fn name(&self) -> Bytes {
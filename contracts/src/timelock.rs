@@ -0,0 +1,89 @@
+use crate::error::Error;
+use crate::storage_types::{DataKey, QueuedOp};
+use soroban_sdk::{Env, Vec};
+
+// Longest delay that can be set, so that a mistaken delay cannot lock the queue for good.
+pub const MAX_DELAY: u64 = 30 * 24 * 60 * 60;
+
+// Delay in seconds between queueing an operation and executing it.
+pub fn read_delay(env: &Env) -> u64 {
+    let key = DataKey::Delay;
+    env.storage().get(key).unwrap_or(Ok(0)).unwrap()
+}
+
+pub fn write_delay(env: &Env, delay: u64) {
+    let key = DataKey::Delay;
+    env.storage().set(key, delay);
+}
+
+pub fn check_delay(delay: u64) -> Result<(), Error> {
+    if delay > MAX_DELAY {
+        return Err(Error::InvalidDelay);
+    }
+    Ok(())
+}
+
+// Returns the timestamp from which an operation queued now can be executed.
+pub fn read_eta(env: &Env) -> Result<u64, Error> {
+    env.ledger()
+        .timestamp()
+        .checked_add(read_delay(env))
+        .ok_or(Error::InvalidDelay)
+}
+
+// Once a delay is set, sensitive admin actions can only go through the queue.
+pub fn check_not_timelocked(env: &Env) -> Result<(), Error> {
+    if read_delay(env) > 0 {
+        return Err(Error::Timelocked);
+    }
+    Ok(())
+}
+
+pub fn read_queue(env: &Env) -> Vec<QueuedOp> {
+    let key = DataKey::Queue;
+    env.storage()
+        .get(key)
+        .unwrap_or_else(|| Ok(Vec::new(env)))
+        .unwrap()
+}
+
+fn write_queue(env: &Env, queue: Vec<QueuedOp>) {
+    let key = DataKey::Queue;
+    env.storage().set(key, queue);
+}
+
+pub fn remove_queue(env: &Env) {
+    let key = DataKey::Queue;
+    env.storage().remove(key);
+}
+
+pub fn next_op_id(env: &Env) -> u32 {
+    let key = DataKey::NextOpId;
+    let id: u32 = env.storage().get(key.clone()).unwrap_or(Ok(1)).unwrap();
+    env.storage().set(key, id + 1);
+    id
+}
+
+pub fn add_queued(env: &Env, op: QueuedOp) {
+    let mut queue = read_queue(env);
+    queue.push_back(op);
+    write_queue(env, queue);
+}
+
+pub fn read_queued(env: &Env, id: u32) -> Option<QueuedOp> {
+    read_queue(env)
+        .iter()
+        .map(|op| op.unwrap())
+        .find(|op| op.id == id)
+}
+
+pub fn remove_queued(env: &Env, id: u32) -> Result<(), Error> {
+    let mut queue = read_queue(env);
+    let index = queue
+        .iter()
+        .position(|op| op.unwrap().id == id)
+        .ok_or(Error::NotQueued)?;
+    queue.remove(index as u32);
+    write_queue(env, queue);
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use crate::storage_types::DataKey;
+use soroban_sdk::Env;
+
+// Bumped whenever the storage layout changes, so that a later version can tell which layout
+// a contract was initialized with. Contracts initialized before versioning read as 0.
+pub const STORAGE_VERSION: u32 = 2;
+
+pub fn read_version(env: &Env) -> u32 {
+    let key = DataKey::Version;
+    env.storage().get(key).unwrap_or(Ok(0)).unwrap()
+}
+
+pub fn write_version(env: &Env, version: u32) {
+    let key = DataKey::Version;
+    env.storage().set(key, version);
+}